@@ -0,0 +1,71 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use crate::settings::Settings;
+use crate::{GameState, WinGame};
+
+/// Toy mode where atoms pull on each other and slowly clump together.
+/// Only active when `Settings::atom_attraction` is set; atoms are spawned as
+/// dynamic bodies in that case.
+pub struct AttractionPlugin;
+
+impl Plugin for AttractionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AttractionConfig>().add_systems(
+            FixedUpdate,
+            attract_atoms.run_if(
+                in_state(GameState::Game).and(|settings: Res<Settings>| settings.atom_attraction),
+            ),
+        );
+    }
+}
+
+#[derive(Resource)]
+pub struct AttractionConfig {
+    /// Acceleration between two atoms one unit apart.
+    pub strength: f32,
+    /// Added to the squared distance so overlapping atoms don't fling apart.
+    pub softening: f32,
+    /// Only this many atoms take part, keeping the O(n²) pass cheap.
+    pub max_atoms: usize,
+}
+
+impl Default for AttractionConfig {
+    fn default() -> Self {
+        Self {
+            strength: 400.0,
+            softening: 4.0,
+            max_atoms: 64,
+        }
+    }
+}
+
+fn attract_atoms(
+    config: Res<AttractionConfig>,
+    time: Res<Time>,
+    mut atoms: Query<(&Position, &mut LinearVelocity), With<WinGame>>,
+) {
+    let positions: Vec<Vec3> = atoms
+        .iter()
+        .take(config.max_atoms)
+        .map(|(position, _)| position.0)
+        .collect();
+
+    let softening_sq = config.softening * config.softening;
+
+    for (index, (position, mut velocity)) in atoms.iter_mut().take(config.max_atoms).enumerate() {
+        let mut acceleration = Vec3::ZERO;
+
+        for (other_index, other) in positions.iter().enumerate() {
+            if other_index == index {
+                continue;
+            }
+
+            let offset = *other - position.0;
+            let distance_sq = offset.length_squared() + softening_sq;
+            acceleration += offset * (config.strength / (distance_sq * distance_sq.sqrt()));
+        }
+
+        velocity.0 += acceleration * time.delta_secs();
+    }
+}
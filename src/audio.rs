@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+use bevy_seedling::prelude::*;
+
+/// Sets up the sampler pools that group sounds for shared volume control.
+/// Sounds without a pool label play through the default pool.
+pub struct AudioPoolsPlugin;
+
+impl Plugin for AudioPoolsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_pools);
+    }
+}
+
+/// Music and musical stings such as the win fanfare.
+#[derive(PoolLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MusicPool;
+
+fn spawn_pools(mut commands: Commands) {
+    commands.spawn(SamplerPool(MusicPool));
+}
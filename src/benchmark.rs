@@ -0,0 +1,333 @@
+use avian3d::prelude::*;
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+use rand::prelude::*;
+
+use crate::graphics::ShadowLight;
+use crate::menu_nav::Modal;
+use crate::{AtomAssets, FontAssets, GameState, NORMAL_BUTTON};
+
+/// Frames in the first moments are dominated by pipeline compilation and
+/// asset uploads, so they're left out of the results.
+const WARMUP_SECS: f32 = 2.0;
+
+/// A fixed, heavy scene flown through by a scripted camera, reporting the
+/// average and 1% low frame rate at the end. Lets players compare graphics
+/// settings on their own hardware. Reads the frame times that
+/// `FrameTimeDiagnosticsPlugin`, added alongside the window, records.
+pub struct BenchmarkPlugin;
+
+impl Plugin for BenchmarkPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BenchmarkConfig>()
+            .add_systems(OnEnter(GameState::Benchmark), start_benchmark)
+            .add_systems(
+                Update,
+                (
+                    benchmark_button_system
+                        .run_if(in_state(GameState::Menu).or(in_state(GameState::Benchmark))),
+                    (run_benchmark, leave_benchmark)
+                        .run_if(in_state(GameState::Benchmark).and(resource_exists::<Benchmark>)),
+                ),
+            )
+            .add_systems(OnExit(GameState::Benchmark), end_benchmark);
+    }
+}
+
+#[derive(Resource)]
+pub struct BenchmarkConfig {
+    /// Atoms along each side of the square atom grid.
+    pub atoms_per_side: u32,
+    pub atom_spacing: f32,
+    /// Loose balls dropped onto the field, to keep the physics busy.
+    pub debris: u32,
+    /// Length of the measured flythrough, after the warmup.
+    pub duration: f32,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            atoms_per_side: 24,
+            atom_spacing: 12.0,
+            debris: 400,
+            duration: 20.0,
+        }
+    }
+}
+
+#[derive(Resource)]
+struct Benchmark {
+    elapsed: f32,
+    /// Half the width of the atom grid.
+    extent: f32,
+    /// Frame times in milliseconds, as reported by the frame time diagnostic.
+    frame_times: Vec<f64>,
+    finished: bool,
+}
+
+#[derive(Component)]
+struct BenchmarkEntity;
+
+#[derive(Component)]
+struct BenchmarkCamera;
+
+#[derive(Component)]
+struct BenchmarkResults;
+
+#[derive(Component)]
+struct BenchmarkResultsText;
+
+#[derive(Component, Clone, Copy)]
+pub enum BenchmarkMenuAction {
+    Open,
+    Back,
+}
+
+fn benchmark_button_system(
+    interaction_query: Query<
+        (&Interaction, &BenchmarkMenuAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, action) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match *action {
+            BenchmarkMenuAction::Open => next_state.set(GameState::Benchmark),
+            BenchmarkMenuAction::Back => next_state.set(GameState::Menu),
+        }
+    }
+}
+
+fn start_benchmark(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<BenchmarkConfig>,
+    atom_assets: Res<AtomAssets>,
+    font_assets: Res<FontAssets>,
+) {
+    // The same scene every time, so results are comparable between runs.
+    let mut rng = StdRng::seed_from_u64(0);
+    let extent = config.atoms_per_side as f32 * config.atom_spacing / 2.0;
+
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(extent * 4.0, extent * 4.0))),
+        MeshMaterial3d(materials.add(Color::WHITE)),
+        RigidBody::Static,
+        Collider::half_space(Vec3::Y),
+        BenchmarkEntity,
+    ));
+
+    for i in 0..config.atoms_per_side {
+        for j in 0..config.atoms_per_side {
+            let position = Vec3::new(
+                i as f32 * config.atom_spacing - extent,
+                4.0,
+                j as f32 * config.atom_spacing - extent,
+            );
+            commands.spawn((
+                SceneRoot(atom_assets.u_atom.clone()),
+                Transform::from_translation(position).looking_to(Vec3::Z, Vec3::Y),
+                RigidBody::Static,
+                Collider::sphere(4.0),
+                BenchmarkEntity,
+            ));
+        }
+    }
+
+    let debris_mesh = meshes.add(Sphere { radius: 0.5 });
+    let debris_material = materials.add(Color::srgb(0.9, 0.5, 0.1));
+    for _ in 0..config.debris {
+        // Spread out in height so balls keep landing through the whole run.
+        let position = Vec3::new(
+            rng.random_range(-extent..extent),
+            rng.random_range(20.0..400.0),
+            rng.random_range(-extent..extent),
+        );
+        commands.spawn((
+            Mesh3d(debris_mesh.clone()),
+            MeshMaterial3d(debris_material.clone()),
+            Transform::from_translation(position),
+            RigidBody::Dynamic,
+            Collider::sphere(0.5),
+            Restitution::new(0.6),
+            BenchmarkEntity,
+        ));
+    }
+
+    commands.spawn((
+        DirectionalLight {
+            illuminance: 4000.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        ShadowLight,
+        Transform::default().looking_to(Vec3::new(0.3, -1.0, 0.2), Vec3::Z),
+        BenchmarkEntity,
+    ));
+
+    // Drawn over the menu camera, like the intro.
+    let camera = commands
+        .spawn((
+            Camera {
+                order: 1,
+                ..default()
+            },
+            Camera3d::default(),
+            Transform::from_translation(camera_position(0.0, extent))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            BenchmarkCamera,
+            BenchmarkEntity,
+        ))
+        .id();
+
+    commands.spawn((
+        BenchmarkResults,
+        Modal,
+        UiTargetCamera(camera),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(10.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        Visibility::Hidden,
+        BenchmarkEntity,
+        children![
+            (
+                BenchmarkResultsText,
+                Text::default(),
+                TextFont {
+                    font: font_assets.u_atom.clone(),
+                    font_size: 50.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextLayout::new_with_justify(JustifyText::Center),
+                TextShadow::default(),
+            ),
+            (
+                Button,
+                BenchmarkMenuAction::Back,
+                Node {
+                    width: Val::Px(300.0),
+                    height: Val::Px(80.0),
+                    border: UiRect::all(Val::Px(5.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BorderColor(Color::BLACK),
+                BorderRadius::MAX,
+                BackgroundColor(NORMAL_BUTTON),
+                children![(
+                    Text::new("Back"),
+                    TextFont {
+                        font: font_assets.u_atom.clone(),
+                        font_size: 38.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    TextShadow::default(),
+                )]
+            ),
+        ],
+    ));
+
+    commands.insert_resource(Benchmark {
+        elapsed: 0.0,
+        extent,
+        frame_times: Vec::new(),
+        finished: false,
+    });
+}
+
+/// One slow loop around the field, dipping low over the atoms and climbing
+/// back out, so both near and far detail get drawn.
+fn camera_position(progress: f32, extent: f32) -> Vec3 {
+    let angle = progress * std::f32::consts::TAU;
+    let radius = extent * (1.1 - 0.5 * (angle * 2.0).sin().abs());
+    let height = 20.0 + 60.0 * (angle * 1.5).cos().abs();
+    Vec3::new(angle.cos() * radius, height, angle.sin() * radius)
+}
+
+fn run_benchmark(
+    mut benchmark: ResMut<Benchmark>,
+    mut camera: Single<&mut Transform, With<BenchmarkCamera>>,
+    mut results: Single<&mut Visibility, With<BenchmarkResults>>,
+    mut results_text: Single<&mut Text, With<BenchmarkResultsText>>,
+    config: Res<BenchmarkConfig>,
+    diagnostics: Res<DiagnosticsStore>,
+    time: Res<Time>,
+) {
+    if benchmark.finished {
+        return;
+    }
+
+    benchmark.elapsed += time.delta_secs();
+    let measured = benchmark.elapsed - WARMUP_SECS;
+
+    let progress = (measured / config.duration).clamp(0.0, 1.0);
+    let position = camera_position(progress, benchmark.extent);
+    **camera = Transform::from_translation(position).looking_at(Vec3::Y * 4.0, Vec3::Y);
+
+    if measured <= 0.0 {
+        return;
+    }
+
+    if let Some(frame_time) = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .and_then(|diagnostic| diagnostic.measurement())
+    {
+        benchmark.frame_times.push(frame_time.value);
+    }
+
+    if measured < config.duration {
+        return;
+    }
+
+    benchmark.finished = true;
+    let (average, low) = frame_rates(&mut benchmark.frame_times);
+    results_text.0 = format!("Average: {average:.0} FPS\n1% low: {low:.0} FPS");
+    **results = Visibility::Inherited;
+}
+
+/// Average and 1% low frame rates from frame times in milliseconds. The 1%
+/// low is the frame rate over the slowest hundredth of frames.
+fn frame_rates(frame_times: &mut [f64]) -> (f64, f64) {
+    if frame_times.is_empty() {
+        return (0.0, 0.0);
+    }
+
+    let average = frame_times.iter().sum::<f64>() / frame_times.len() as f64;
+
+    frame_times.sort_by(|a, b| b.total_cmp(a));
+    let slowest = &frame_times[..frame_times.len().div_ceil(100)];
+    let low = slowest.iter().sum::<f64>() / slowest.len() as f64;
+
+    (1000.0 / average, 1000.0 / low)
+}
+
+fn leave_benchmark(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::Menu);
+    }
+}
+
+fn end_benchmark(mut commands: Commands, query: Query<Entity, With<BenchmarkEntity>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+    commands.remove_resource::<Benchmark>();
+}
@@ -0,0 +1,127 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::countdown::RunTimer;
+use crate::settings::{DifficultyConfig, Settings};
+use crate::{AtomAssets, AtomValue, GameState, spawn_atom};
+
+/// Bonus atoms are kept at least this far from the level's own atoms.
+const BONUS_SPACING: f32 = 10.0;
+
+/// A few extra atoms worth more than the rest that fizzle out if they
+/// aren't collected soon after "Go". They're optional: they don't count
+/// towards critical mass or the level's atom total.
+pub struct BonusAtomPlugin;
+
+impl Plugin for BonusAtomPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<AtomExpired>()
+            .init_resource::<BonusAtomConfig>()
+            .add_systems(Update, expire_atoms.run_if(in_state(GameState::Game)));
+    }
+}
+
+#[derive(Resource)]
+pub struct BonusAtomConfig {
+    pub count: u32,
+    /// How long each one lasts once the run starts, at normal difficulty.
+    pub lifetime: Duration,
+    /// Score for collecting one, where an ordinary atom is worth 1.
+    pub value: u32,
+    /// Bonus atoms are scattered between these distances from the player's
+    /// spawn point.
+    pub min_distance: f32,
+    pub max_distance: f32,
+}
+
+impl Default for BonusAtomConfig {
+    fn default() -> Self {
+        Self {
+            count: 3,
+            lifetime: Duration::from_secs(15),
+            value: 5,
+            min_distance: 15.0,
+            max_distance: 45.0,
+        }
+    }
+}
+
+/// Vanishes when the timer runs out.
+#[derive(Component, Clone)]
+pub struct TimedAtom(pub Timer);
+
+/// Sent when a bonus atom runs out of time uncollected.
+#[derive(Event)]
+pub struct AtomExpired {
+    pub position: Vec3,
+}
+
+/// Places the bonus atoms clear of `atoms`, in the same spots for the same
+/// seed.
+pub fn spawn_bonus_atoms(
+    commands: &mut Commands,
+    atom_assets: &AtomAssets,
+    settings: &Settings,
+    config: &BonusAtomConfig,
+    seed: u64,
+    spawn_point: Vec3,
+    atoms: &[Vec3],
+) {
+    let mut rng = StdRng::seed_from_u64(seed.wrapping_add(1));
+    let mut placed: Vec<Vec3> = Vec::new();
+
+    for _ in 0..config.count * 50 {
+        if placed.len() as u32 >= config.count {
+            break;
+        }
+
+        let angle = rng.random_range(0.0..std::f32::consts::TAU);
+        let distance = rng.random_range(config.min_distance..config.max_distance);
+        let position =
+            spawn_point.with_y(4.0) + Vec3::new(angle.cos(), 0.0, angle.sin()) * distance;
+        if atoms
+            .iter()
+            .chain(&placed)
+            .any(|other| other.distance(position) < BONUS_SPACING)
+        {
+            continue;
+        }
+
+        let atom = spawn_atom(commands, atom_assets, settings, position);
+        commands.entity(atom).insert((
+            AtomValue(config.value),
+            TimedAtom(Timer::new(config.lifetime, TimerMode::Once)),
+        ));
+        placed.push(position);
+    }
+}
+
+/// Only counts down once the run is under way, so the countdown doesn't eat
+/// into it.
+fn expire_atoms(
+    mut commands: Commands,
+    time: Res<Time>,
+    run_timer: Res<RunTimer>,
+    settings: Res<Settings>,
+    difficulty: Res<DifficultyConfig>,
+    mut atoms: Query<(Entity, &mut TimedAtom, &GlobalTransform)>,
+    mut expired: EventWriter<AtomExpired>,
+) {
+    if !run_timer.running {
+        return;
+    }
+
+    let delta = time
+        .delta()
+        .mul_f32(difficulty.tuning(settings.difficulty).timer_speed);
+    for (entity, mut timed, transform) in atoms.iter_mut() {
+        if timed.0.tick(delta).just_finished() {
+            commands.entity(entity).despawn();
+            expired.write(AtomExpired {
+                position: transform.translation(),
+            });
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use bevy_third_person_camera::ThirdPersonCamera;
+
+use crate::levels::{LevelConfig, Levels, SelectedLevel};
+use crate::settings::Settings;
+use crate::{GameState, Menu, Player};
+
+/// Glows along the edge of the screen facing a nearby wall of the play
+/// area, stronger the closer the player gets, so the edge of a big level
+/// doesn't come as a surprise.
+pub struct BoundaryPlugin;
+
+impl Plugin for BoundaryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BoundaryConfig>().add_systems(
+            Update,
+            (
+                setup_edge_warnings
+                    .run_if(in_state(GameState::Game).and(not(any_with_component::<EdgeWarning>))),
+                update_edge_warnings.run_if(in_state(GameState::Game)),
+            ),
+        );
+    }
+}
+
+#[derive(Resource)]
+pub struct BoundaryConfig {
+    /// Distance from a wall at which the warning starts to show.
+    pub warn_distance: f32,
+    pub color: Color,
+    /// Opacity of an edge with the player right against the wall.
+    pub max_alpha: f32,
+    /// Pulses per second.
+    pub pulse_rate: f32,
+    /// How quickly an edge eases towards its new strength. Higher is faster.
+    pub fade_rate: f32,
+    /// Thickness of each edge, in pixels.
+    pub thickness: f32,
+}
+
+impl Default for BoundaryConfig {
+    fn default() -> Self {
+        Self {
+            warn_distance: 40.0,
+            color: Color::srgb(1.0, 0.3, 0.2),
+            max_alpha: 0.45,
+            pulse_rate: 1.5,
+            fade_rate: 6.0,
+            thickness: 80.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ScreenEdge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+#[derive(Component)]
+struct EdgeWarning {
+    edge: ScreenEdge,
+    /// Current strength from 0 to 1, before pulsing.
+    strength: f32,
+}
+
+fn setup_edge_warnings(
+    mut commands: Commands,
+    config: Res<BoundaryConfig>,
+    camera: Single<Entity, With<ThirdPersonCamera>>,
+) {
+    let thickness = Val::Px(config.thickness);
+    let strip = |edge: ScreenEdge| {
+        let mut node = Node {
+            position_type: PositionType::Absolute,
+            ..default()
+        };
+        match edge {
+            ScreenEdge::Top | ScreenEdge::Bottom => {
+                node.width = Val::Percent(100.0);
+                node.height = thickness;
+            }
+            ScreenEdge::Left | ScreenEdge::Right => {
+                node.width = thickness;
+                node.height = Val::Percent(100.0);
+            }
+        }
+        match edge {
+            ScreenEdge::Top => node.top = Val::ZERO,
+            ScreenEdge::Bottom => node.bottom = Val::ZERO,
+            ScreenEdge::Left => node.left = Val::ZERO,
+            ScreenEdge::Right => node.right = Val::ZERO,
+        }
+        (
+            EdgeWarning {
+                edge,
+                strength: 0.0,
+            },
+            node,
+            BackgroundColor(Color::NONE),
+        )
+    };
+
+    // Spawned once and kept, like the HUD, only shown during play.
+    commands.spawn((
+        Menu {
+            show_state: GameState::Game,
+        },
+        UiTargetCamera(*camera),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        children![
+            strip(ScreenEdge::Top),
+            strip(ScreenEdge::Bottom),
+            strip(ScreenEdge::Left),
+            strip(ScreenEdge::Right),
+        ],
+    ));
+}
+
+fn update_edge_warnings(
+    config: Res<BoundaryConfig>,
+    level_config: Res<LevelConfig>,
+    levels: Res<Levels>,
+    selected: Res<SelectedLevel>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+    player: Query<&GlobalTransform, With<Player>>,
+    camera: Query<&GlobalTransform, With<ThirdPersonCamera>>,
+    mut edges: Query<(&mut EdgeWarning, &mut BackgroundColor)>,
+) {
+    let (Ok(player), Ok(camera)) = (player.single(), camera.single()) else {
+        return;
+    };
+
+    // How near each wall is, as a level direction scaled from 0 (out of
+    // range) to 1 (touching). Near a corner two walls add up.
+    let wall_distance = level_config.wall_distance(selected.get(&levels));
+    let position = player.translation();
+    let closeness = |coordinate: f32| {
+        (1.0 - (wall_distance - coordinate) / config.warn_distance.max(f32::EPSILON))
+            .clamp(0.0, 1.0)
+    };
+    let toward_walls = Vec3::X * (closeness(position.x) - closeness(-position.x))
+        + Vec3::Z * (closeness(position.z) - closeness(-position.z));
+
+    // Turned to the screen: ahead shows at the top, behind at the bottom.
+    let forward = camera.forward().as_vec3().with_y(0.0).normalize_or_zero();
+    let right = camera.right().as_vec3().with_y(0.0).normalize_or_zero();
+    let ahead = toward_walls.dot(forward);
+    let aside = toward_walls.dot(right);
+
+    let blend = 1.0 - (-config.fade_rate * time.delta_secs()).exp();
+    let pulse = if settings.reduce_motion {
+        1.0
+    } else {
+        0.75 + 0.25 * (time.elapsed_secs() * config.pulse_rate * TAU).sin()
+    };
+    for (mut warning, mut background) in edges.iter_mut() {
+        let target = match warning.edge {
+            ScreenEdge::Top => ahead,
+            ScreenEdge::Bottom => -ahead,
+            ScreenEdge::Left => -aside,
+            ScreenEdge::Right => aside,
+        }
+        .clamp(0.0, 1.0);
+        warning.strength = warning.strength.lerp(target, blend);
+
+        let alpha = warning.strength * pulse * config.max_alpha;
+        background.0 = config.color.with_alpha(alpha);
+    }
+}
@@ -0,0 +1,184 @@
+use std::time::Duration;
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::AtomCollected;
+use crate::bonus_atom::AtomExpired;
+use crate::settings::{GraphicsQuality, Settings};
+
+/// A puff of glowing sparks where an atom is collected, and a duller fizzle
+/// where a bonus atom runs out of time.
+pub struct BurstPlugin;
+
+impl Plugin for BurstPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BurstConfig>()
+            .add_systems(Startup, setup_burst_assets)
+            .add_systems(
+                Update,
+                (
+                    spawn_bursts.run_if(on_event::<AtomCollected>),
+                    spawn_fizzles.run_if(on_event::<AtomExpired>),
+                    update_sparks,
+                ),
+            );
+    }
+}
+
+#[derive(Resource)]
+pub struct BurstConfig {
+    /// Sparks per atom at low, medium and high graphics quality.
+    pub counts: [usize; 3],
+    pub speed: f32,
+    pub lifetime: Duration,
+}
+
+impl Default for BurstConfig {
+    fn default() -> Self {
+        Self {
+            counts: [4, 8, 16],
+            speed: 12.0,
+            lifetime: Duration::from_millis(600),
+        }
+    }
+}
+
+/// Shared by every spark.
+#[derive(Resource)]
+struct BurstAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+    /// A gentler glow for `Settings::reduce_motion`.
+    dim_material: Handle<StandardMaterial>,
+    fizzle_material: Handle<StandardMaterial>,
+}
+
+/// Despawns the entity once the timer runs out.
+#[derive(Component)]
+pub struct Lifetime(pub Timer);
+
+fn setup_burst_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(BurstAssets {
+        mesh: meshes.add(Sphere { radius: 0.25 }),
+        material: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.25, 0.9, 0.2),
+            emissive: LinearRgba::rgb(1.0, 4.0, 0.8),
+            ..default()
+        }),
+        dim_material: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.25, 0.9, 0.2),
+            emissive: LinearRgba::rgb(0.1, 0.4, 0.08),
+            ..default()
+        }),
+        fizzle_material: materials.add(Color::srgb(0.4, 0.4, 0.4)),
+    });
+}
+
+fn spawn_bursts(
+    mut commands: Commands,
+    mut collected: EventReader<AtomCollected>,
+    config: Res<BurstConfig>,
+    settings: Res<Settings>,
+    assets: Res<BurstAssets>,
+) {
+    let count = spark_count(&config, &settings);
+    let material = if settings.reduce_motion {
+        &assets.dim_material
+    } else {
+        &assets.material
+    };
+    for event in collected.read() {
+        spawn_sparks(
+            &mut commands,
+            &config,
+            &assets.mesh,
+            material,
+            event.position,
+            count,
+            config.speed,
+        );
+    }
+}
+
+/// Half as many sparks at half the speed, in grey.
+fn spawn_fizzles(
+    mut commands: Commands,
+    mut expired: EventReader<AtomExpired>,
+    config: Res<BurstConfig>,
+    settings: Res<Settings>,
+    assets: Res<BurstAssets>,
+) {
+    let count = spark_count(&config, &settings) / 2;
+    for event in expired.read() {
+        spawn_sparks(
+            &mut commands,
+            &config,
+            &assets.mesh,
+            &assets.fizzle_material,
+            event.position,
+            count,
+            config.speed / 2.0,
+        );
+    }
+}
+
+fn spark_count(config: &BurstConfig, settings: &Settings) -> usize {
+    match settings.graphics_quality {
+        GraphicsQuality::Low => config.counts[0],
+        GraphicsQuality::Medium => config.counts[1],
+        GraphicsQuality::High => config.counts[2],
+    }
+}
+
+fn spawn_sparks(
+    commands: &mut Commands,
+    config: &BurstConfig,
+    mesh: &Handle<Mesh>,
+    material: &Handle<StandardMaterial>,
+    position: Vec3,
+    count: usize,
+    speed: f32,
+) {
+    let mut rng = rand::rng();
+    for _ in 0..count {
+        // Mostly outwards and upwards, so little goes into the ground.
+        let direction = Vec3::new(
+            rng.random_range(-1.0..1.0),
+            rng.random_range(0.2..1.0),
+            rng.random_range(-1.0..1.0),
+        )
+        .normalize_or(Vec3::Y);
+
+        commands.spawn((
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material.clone()),
+            Transform::from_translation(position),
+            // Kinematic, so the sparks fly straight and never knock into
+            // the player.
+            RigidBody::Kinematic,
+            LinearVelocity(direction * speed * rng.random_range(0.5..1.0)),
+            Lifetime(Timer::new(config.lifetime, TimerMode::Once)),
+        ));
+    }
+}
+
+/// Shrinks sparks as they age and removes them when they're done.
+fn update_sparks(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut sparks: Query<(Entity, &mut Lifetime, &mut Transform)>,
+) {
+    for (entity, mut lifetime, mut transform) in sparks.iter_mut() {
+        if lifetime.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        transform.scale = Vec3::splat(lifetime.0.fraction_remaining());
+    }
+}
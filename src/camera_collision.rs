@@ -0,0 +1,89 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+use bevy_third_person_camera::{CameraSyncSet, ThirdPersonCamera, ThirdPersonCameraTarget};
+
+/// Keeps the third person camera from clipping through the ground and atoms
+/// by shortening its boom to the first obstruction between it and the player.
+pub struct CameraCollisionPlugin;
+
+impl Plugin for CameraCollisionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraCollisionConfig>().add_systems(
+            PostUpdate,
+            pull_camera_in
+                .after(CameraSyncSet)
+                .before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+#[derive(Resource)]
+pub struct CameraCollisionConfig {
+    /// How far in front of an obstruction the camera stops.
+    pub margin: f32,
+    /// The boom never gets shorter than this, so the camera doesn't end up
+    /// inside the ball.
+    pub min_distance: f32,
+    /// How quickly the boom eases back out once the obstruction clears.
+    /// Higher is faster.
+    pub restore_rate: f32,
+}
+
+impl Default for CameraCollisionConfig {
+    fn default() -> Self {
+        Self {
+            margin: 0.5,
+            min_distance: 1.0,
+            restore_rate: 6.0,
+        }
+    }
+}
+
+/// Runs after the camera plugin has placed the camera at its full zoom
+/// radius and offset, and moves it back along the same line towards the
+/// player.
+fn pull_camera_in(
+    mut boom: Local<Option<f32>>,
+    config: Res<CameraCollisionConfig>,
+    spatial_query: SpatialQuery,
+    time: Res<Time>,
+    player: Query<(Entity, &Transform), With<ThirdPersonCameraTarget>>,
+    mut camera: Query<&mut Transform, (With<ThirdPersonCamera>, Without<ThirdPersonCameraTarget>)>,
+) {
+    let Ok((player, player_transform)) = player.single() else {
+        return;
+    };
+    let Ok(mut camera_transform) = camera.single_mut() else {
+        return;
+    };
+
+    let origin = player_transform.translation;
+    let Ok((direction, desired)) = Dir3::new_and_length(camera_transform.translation - origin)
+    else {
+        return;
+    };
+
+    let filter = SpatialQueryFilter::from_excluded_entities([player]);
+    let allowed = spatial_query
+        .cast_ray(origin, direction, desired, true, &filter)
+        .map_or(desired, |hit| {
+            (hit.distance - config.margin).max(config.min_distance)
+        })
+        .min(desired);
+
+    // Snap in straight away so the camera never shows through geometry,
+    // but ease back out so it doesn't pop when the view clears.
+    let current = boom.unwrap_or(desired);
+    let distance = if allowed < current {
+        allowed
+    } else {
+        current.lerp(
+            allowed,
+            1.0 - (-config.restore_rate * time.delta_secs()).exp(),
+        )
+    };
+    *boom = Some(distance);
+
+    camera_transform.translation = origin + direction * distance;
+}
@@ -0,0 +1,182 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_third_person_camera::ThirdPersonCamera;
+use bevy_tnua::{TnuaPipelineStages, TnuaProximitySensor};
+
+use crate::theme::ColorTheme;
+use crate::{AtomCollectionSet, CollisionWith, GameState, Player, WinGame};
+
+const BAR_SIZE: Vec2 = Vec2::new(60.0, 10.0);
+
+/// Optional capture mechanic: the player has to stay in contact with an atom
+/// for `CaptureConfig::dwell_secs` before it's collected. Breaking contact
+/// resets the atom's progress.
+pub struct CapturePlugin;
+
+impl Plugin for CapturePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CaptureConfig>()
+            // Timed on the fixed step alongside collection, reading the
+            // ground sensor once it's been refreshed.
+            .add_systems(
+                FixedUpdate,
+                track_capture
+                    .after(TnuaPipelineStages::Sensors)
+                    .before(AtomCollectionSet)
+                    .run_if(in_state(GameState::Game).and(dwell_enabled)),
+            )
+            .add_systems(Update, update_capture_bars);
+    }
+}
+
+#[derive(Resource)]
+pub struct CaptureConfig {
+    /// Seconds of unbroken contact needed to collect an atom. Zero collects
+    /// on first touch.
+    pub dwell_secs: f32,
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self { dwell_secs: 0.0 }
+    }
+}
+
+impl CaptureConfig {
+    /// Whether an atom with this capture progress may be collected.
+    pub fn allows_collection(&self, capture: Option<&Capture>) -> bool {
+        self.dwell_secs <= 0.0 || capture.is_some_and(|capture| capture.elapsed >= self.dwell_secs)
+    }
+}
+
+/// Progress on an atom the player is touching.
+#[derive(Component)]
+pub struct Capture {
+    elapsed: f32,
+    bar: Entity,
+}
+
+/// The frame of the fill indicator drawn over a capturing atom.
+#[derive(Component)]
+struct CaptureBar {
+    atom: Entity,
+}
+
+#[derive(Component)]
+struct CaptureFill;
+
+fn dwell_enabled(config: Res<CaptureConfig>) -> bool {
+    config.dwell_secs > 0.0
+}
+
+fn track_capture(
+    mut commands: Commands,
+    mut event_collision: EventWriter<CollisionWith>,
+    config: Res<CaptureConfig>,
+    time: Res<Time>,
+    theme: Res<ColorTheme>,
+    camera: Single<Entity, With<ThirdPersonCamera>>,
+    player: Query<(&TnuaProximitySensor, &CollidingEntities), With<Player>>,
+    mut atoms: Query<(Entity, Option<&mut Capture>), With<WinGame>>,
+) {
+    let Ok((sensor, colliding)) = player.single() else {
+        return;
+    };
+
+    // Solid atoms are stood on, so only the ground sensor sees them; sensor
+    // atoms are rolled through and show up as physics contacts.
+    let standing_on = sensor.output.as_ref().map(|output| output.entity);
+    let touching = |atom: Entity| standing_on == Some(atom) || colliding.contains(&atom);
+
+    for (atom, capture) in atoms.iter_mut() {
+        match (touching(atom), capture) {
+            (true, Some(mut capture)) => {
+                capture.elapsed += time.delta_secs();
+                if capture.elapsed >= config.dwell_secs {
+                    event_collision.write(CollisionWith(atom));
+                }
+            }
+            (true, None) => {
+                let bar = commands
+                    .spawn(capture_bar(atom, *camera, theme.highlight))
+                    .id();
+                commands.entity(atom).insert(Capture { elapsed: 0.0, bar });
+            }
+            (false, Some(_)) => {
+                commands.entity(atom).remove::<Capture>();
+            }
+            (false, None) => {}
+        }
+    }
+}
+
+fn capture_bar(atom: Entity, camera: Entity, fill: Color) -> impl Bundle {
+    (
+        CaptureBar { atom },
+        UiTargetCamera(camera),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Px(BAR_SIZE.x),
+            height: Val::Px(BAR_SIZE.y),
+            border: UiRect::all(Val::Px(2.0)),
+            ..default()
+        },
+        BorderColor(Color::BLACK),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+        Visibility::Hidden,
+        children![(
+            CaptureFill,
+            Node {
+                width: Val::Percent(0.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            BackgroundColor(fill),
+        )],
+    )
+}
+
+fn update_capture_bars(
+    mut commands: Commands,
+    config: Res<CaptureConfig>,
+    state: Res<State<GameState>>,
+    camera: Query<(&Camera, &GlobalTransform), With<ThirdPersonCamera>>,
+    atoms: Query<(&GlobalTransform, &Capture)>,
+    mut bars: Query<(Entity, &CaptureBar, &Children, &mut Node, &mut Visibility)>,
+    mut fills: Query<&mut Node, (With<CaptureFill>, Without<CaptureBar>)>,
+) {
+    let camera = camera.single().ok();
+    let show = *state.get() == GameState::Game;
+
+    for (entity, bar, children, mut node, mut visibility) in bars.iter_mut() {
+        // The atom was collected, lost contact, or restarted its capture with
+        // a new bar.
+        let Some((atom, capture)) = atoms
+            .get(bar.atom)
+            .ok()
+            .filter(|(_, capture)| capture.bar == entity)
+        else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+
+        let viewport = camera.filter(|_| show).and_then(|(camera, transform)| {
+            camera.world_to_viewport(transform, atom.translation()).ok()
+        });
+
+        let Some(position) = viewport else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+
+        node.left = Val::Px(position.x - BAR_SIZE.x / 2.0);
+        node.top = Val::Px(position.y - BAR_SIZE.y / 2.0);
+        visibility.set_if_neq(Visibility::Inherited);
+
+        let progress = (capture.elapsed / config.dwell_secs).clamp(0.0, 1.0);
+        let mut fills = fills.iter_many_mut(children);
+        while let Some(mut fill) = fills.fetch_next() {
+            fill.width = Val::Percent(progress * 100.0);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, render::view::RenderLayers, window::PrimaryWindow};
+use bevy_seedling::prelude::*;
+use rand::Rng;
+
+use crate::audio::MusicPool;
+use crate::settings::{GraphicsQuality, Settings};
+use crate::{GameState, SoundAssets};
+
+/// Confetti and a fanfare when the win screen comes up.
+pub struct CelebrationPlugin;
+
+impl Plugin for CelebrationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CelebrationConfig>()
+            .add_systems(OnEnter(GameState::Win), celebrate)
+            .add_systems(Update, update_confetti.run_if(in_state(GameState::Win)))
+            .add_systems(OnExit(GameState::Win), clear_confetti);
+    }
+}
+
+#[derive(Resource)]
+pub struct CelebrationConfig {
+    pub lifetime: Duration,
+    /// Confetti pieces at low, medium and high graphics quality.
+    pub counts: [usize; 3],
+    /// Divides the count when `Settings::reduce_motion` is on.
+    pub reduce_motion_divisor: usize,
+}
+
+impl Default for CelebrationConfig {
+    fn default() -> Self {
+        Self {
+            lifetime: Duration::from_secs(4),
+            counts: [30, 80, 160],
+            reduce_motion_divisor: 4,
+        }
+    }
+}
+
+#[derive(Component)]
+struct Confetti {
+    velocity: Vec2,
+    spin: f32,
+    timer: Timer,
+}
+
+fn celebrate(
+    mut commands: Commands,
+    window: Single<&Window, With<PrimaryWindow>>,
+    config: Res<CelebrationConfig>,
+    settings: Res<Settings>,
+    sound_assets: Res<SoundAssets>,
+) {
+    commands.spawn((
+        MusicPool,
+        SamplePlayer::new(sound_assets.fanfare.clone()).with_volume(Volume::Decibels(-6.0)),
+    ));
+
+    let mut count = match settings.graphics_quality {
+        GraphicsQuality::Low => config.counts[0],
+        GraphicsQuality::Medium => config.counts[1],
+        GraphicsQuality::High => config.counts[2],
+    };
+    if settings.reduce_motion {
+        count /= config.reduce_motion_divisor.max(1);
+    }
+
+    // The menu camera is a default 2D camera, so world units are pixels with
+    // the origin at the centre of the screen.
+    let half = window.size() / 2.0;
+    let mut rng = rand::rng();
+
+    for _ in 0..count {
+        let color = Color::hsl(rng.random_range(0.0..360.0), 0.9, 0.6);
+        let position = Vec2::new(
+            rng.random_range(-half.x..half.x),
+            half.y + rng.random_range(10.0..200.0),
+        );
+
+        commands.spawn((
+            Confetti {
+                velocity: Vec2::new(
+                    rng.random_range(-60.0..60.0),
+                    -rng.random_range(120.0..260.0),
+                ),
+                spin: rng.random_range(-8.0..8.0),
+                timer: Timer::new(config.lifetime, TimerMode::Once),
+            },
+            Sprite::from_color(color, Vec2::new(12.0, 6.0)),
+            Transform::from_translation(position.extend(0.0)).with_rotation(Quat::from_rotation_z(
+                rng.random_range(0.0..std::f32::consts::TAU),
+            )),
+            RenderLayers::layer(1),
+        ));
+    }
+}
+
+fn update_confetti(
+    mut commands: Commands,
+    mut confetti: Query<(Entity, &mut Confetti, &mut Transform)>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, mut piece, mut transform) in confetti.iter_mut() {
+        if piece.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        // A little sideways flutter as it falls.
+        let flutter = (piece.timer.elapsed_secs() * 4.0 + piece.spin).sin() * 40.0;
+        transform.translation += Vec3::new(piece.velocity.x + flutter, piece.velocity.y, 0.0) * dt;
+        transform.rotate_z(piece.spin * dt);
+    }
+}
+
+fn clear_confetti(mut commands: Commands, confetti: Query<Entity, With<Confetti>>) {
+    for entity in confetti.iter() {
+        commands.entity(entity).despawn();
+    }
+}
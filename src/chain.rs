@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{AtomCollectionSet, CollisionWith, GameState};
+
+/// Chain reactions: a collected atom sets off the atoms around it, which go
+/// off after a short delay and set off their own neighbours in turn.
+pub struct ChainPlugin;
+
+impl Plugin for ChainPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ChainConfig>()
+            .init_resource::<ChainReaction>()
+            // On the fixed step, so fuses burn at the same rate as the combo
+            // window they feed.
+            .add_systems(
+                FixedUpdate,
+                burn_fuses
+                    .before(AtomCollectionSet)
+                    .run_if(in_state(GameState::Game)),
+            )
+            .add_systems(
+                PostUpdate,
+                end_chain.run_if(not(any_with_component::<ChainFuse>)),
+            );
+    }
+}
+
+#[derive(Resource)]
+pub struct ChainConfig {
+    /// Atoms closer than this to a collected one are set off. Zero turns
+    /// chain reactions off.
+    pub radius: f32,
+    /// Time between an atom being set off and it going off.
+    pub delay: Duration,
+}
+
+impl Default for ChainConfig {
+    fn default() -> Self {
+        Self {
+            // About one and a half grid spacings: reaches the grid's nearest
+            // atoms, diagonals included, and a scatter's close neighbours.
+            // The ring's atoms are too far apart to chain.
+            radius: 13.5,
+            delay: Duration::from_millis(150),
+        }
+    }
+}
+
+/// The cascade currently going off.
+#[derive(Resource, Default)]
+pub struct ChainReaction {
+    /// Atoms set off by their neighbours so far. Back to zero once the last
+    /// fuse has burnt down.
+    pub length: u32,
+    /// Where the most recently chained atom went off.
+    pub front: Vec3,
+}
+
+/// An atom that's been set off by a neighbour and will be collected when the
+/// timer runs out.
+#[derive(Component)]
+pub struct ChainFuse {
+    timer: Timer,
+}
+
+impl ChainFuse {
+    pub fn new(config: &ChainConfig) -> Self {
+        Self {
+            timer: Timer::new(config.delay, TimerMode::Once),
+        }
+    }
+}
+
+fn burn_fuses(
+    mut event_collision: EventWriter<CollisionWith>,
+    mut fuses: Query<(Entity, &mut ChainFuse)>,
+    time: Res<Time>,
+) {
+    for (atom, mut fuse) in fuses.iter_mut() {
+        if fuse.timer.tick(time.delta()).just_finished() {
+            event_collision.write(CollisionWith(atom));
+        }
+    }
+}
+
+fn end_chain(mut chain: ResMut<ChainReaction>) {
+    if chain.length > 0 {
+        chain.length = 0;
+    }
+}
@@ -0,0 +1,192 @@
+use bevy::prelude::*;
+use bevy_third_person_camera::ThirdPersonCamera;
+
+use crate::GameState;
+use crate::chain::ChainReaction;
+use crate::settings::Settings;
+
+/// A short cut-away for long chain reactions: a second camera takes over from
+/// the player's view, follows the chain front across the atom field and
+/// eases back once the chain dies out. Any key or click cuts straight back.
+pub struct ChainCameraPlugin;
+
+impl Plugin for ChainCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ChainCameraConfig>()
+            .add_systems(
+                Update,
+                (
+                    start_chain_shot.run_if(not(resource_exists::<ChainShot>)),
+                    (play_chain_shot, skip_chain_shot)
+                        .chain()
+                        .run_if(resource_exists::<ChainShot>),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Game)),
+            )
+            .add_systems(OnExit(GameState::Game), end_chain_shot);
+    }
+}
+
+#[derive(Resource)]
+pub struct ChainCameraConfig {
+    /// Chain length at which the camera cuts away to follow it.
+    pub min_length: u32,
+    /// Where the camera sits relative to the chain front.
+    pub offset: Vec3,
+    /// How quickly the view catches up with the chain front. Higher is
+    /// faster.
+    pub follow_rate: f32,
+    /// Time taken to move between the player's view and the chain.
+    pub blend_secs: f32,
+    /// How long to keep watching after the last atom goes off.
+    pub linger_secs: f32,
+    /// The cut-away never lasts longer than this, however long the chain.
+    pub max_secs: f32,
+}
+
+impl Default for ChainCameraConfig {
+    fn default() -> Self {
+        Self {
+            min_length: 6,
+            offset: Vec3::new(0.0, 30.0, -35.0),
+            follow_rate: 4.0,
+            blend_secs: 0.6,
+            linger_secs: 0.8,
+            max_secs: 6.0,
+        }
+    }
+}
+
+#[derive(Resource)]
+struct ChainShot {
+    elapsed: f32,
+    /// The smoothed point being watched.
+    focus: Vec3,
+    /// The player's view when the shot started.
+    from: Transform,
+    /// Time since the chain died out.
+    quiet: f32,
+    /// The shot's pose and elapsed time when it started heading back.
+    returning: Option<(Transform, f32)>,
+}
+
+#[derive(Component)]
+struct ChainCamera;
+
+fn start_chain_shot(
+    mut commands: Commands,
+    mut filmed: Local<bool>,
+    chain: Res<ChainReaction>,
+    config: Res<ChainCameraConfig>,
+    settings: Res<Settings>,
+    player_camera: Single<&Transform, With<ThirdPersonCamera>>,
+) {
+    // One shot per chain.
+    if chain.length == 0 {
+        *filmed = false;
+        return;
+    }
+    if *filmed
+        || chain.length < config.min_length
+        || !settings.chain_camera
+        || settings.reduce_motion
+    {
+        return;
+    }
+    *filmed = true;
+
+    let from = **player_camera;
+    commands.spawn((
+        ChainCamera,
+        // Drawn over the player's camera, which carries on underneath.
+        Camera {
+            order: 1,
+            ..default()
+        },
+        Camera3d::default(),
+        from,
+    ));
+    commands.insert_resource(ChainShot {
+        elapsed: 0.0,
+        focus: chain.front,
+        from,
+        quiet: 0.0,
+        returning: None,
+    });
+}
+
+fn play_chain_shot(
+    mut commands: Commands,
+    mut shot: ResMut<ChainShot>,
+    chain: Res<ChainReaction>,
+    config: Res<ChainCameraConfig>,
+    time: Res<Time>,
+    player_camera: Single<&Transform, (With<ThirdPersonCamera>, Without<ChainCamera>)>,
+    camera: Single<(Entity, &mut Transform), With<ChainCamera>>,
+) {
+    let (entity, mut transform) = camera.into_inner();
+    let dt = time.delta_secs();
+    shot.elapsed += dt;
+    shot.focus = shot
+        .focus
+        .lerp(chain.front, 1.0 - (-config.follow_rate * dt).exp());
+    shot.quiet = if chain.length == 0 {
+        shot.quiet + dt
+    } else {
+        0.0
+    };
+
+    if shot.returning.is_none()
+        && (shot.quiet >= config.linger_secs || shot.elapsed >= config.max_secs)
+    {
+        shot.returning = Some((*transform, shot.elapsed));
+    }
+
+    let follow =
+        Transform::from_translation(shot.focus + config.offset).looking_at(shot.focus, Vec3::Y);
+    *transform = match shot.returning {
+        None => blend(&shot.from, &follow, shot.elapsed / config.blend_secs),
+        Some((from, started)) => {
+            let t = (shot.elapsed - started) / config.blend_secs;
+            if t >= 1.0 {
+                cut_back(&mut commands, entity);
+                return;
+            }
+            blend(&from, &player_camera, t)
+        }
+    };
+}
+
+fn blend(from: &Transform, to: &Transform, t: f32) -> Transform {
+    let t = t.clamp(0.0, 1.0);
+    let t = t * t * (3.0 - 2.0 * t);
+    Transform {
+        translation: from.translation.lerp(to.translation, t),
+        rotation: from.rotation.slerp(to.rotation, t),
+        scale: Vec3::ONE,
+    }
+}
+
+fn skip_chain_shot(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    camera: Single<Entity, With<ChainCamera>>,
+) {
+    if keyboard.get_just_pressed().next().is_some() || mouse.get_just_pressed().next().is_some() {
+        cut_back(&mut commands, *camera);
+    }
+}
+
+/// Skipping and finishing can both happen in one frame, hence `try_despawn`.
+fn cut_back(commands: &mut Commands, camera: Entity) {
+    commands.entity(camera).try_despawn();
+    commands.remove_resource::<ChainShot>();
+}
+
+fn end_chain_shot(mut commands: Commands, cameras: Query<Entity, With<ChainCamera>>) {
+    for entity in cameras.iter() {
+        cut_back(&mut commands, entity);
+    }
+}
@@ -0,0 +1,337 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use crate::coop::AnyPlayer;
+use crate::countdown::RunTimer;
+use crate::settings::{Difficulty, Settings};
+use crate::{GameOver, GameState, LevelEntity, LoseCause, PendingGameOver, Player};
+
+/// Directions tried either side of the straight line to the player when it's
+/// blocked, in radians.
+const STEER_STEP: f32 = std::f32::consts::PI / 12.0;
+const STEER_STEPS: u32 = 8;
+
+/// Optional enemies that home in on the player once they come within range,
+/// steering around atoms in the way. Touching one loses the run.
+pub struct ChaserPlugin;
+
+impl Plugin for ChaserPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ChaserConfig>()
+            .init_resource::<ChaserAssets>()
+            .add_systems(
+                Update,
+                (move_chasers, catch_player)
+                    .chain()
+                    .run_if(in_state(GameState::Game)),
+            );
+    }
+}
+
+/// How dangerous chasers are at one difficulty.
+pub struct ChaserTuning {
+    pub count: u32,
+    pub speed: f32,
+    /// Chasers stay put until the player comes this close.
+    pub detection_range: f32,
+}
+
+#[derive(Resource)]
+pub struct ChaserConfig {
+    pub easy: ChaserTuning,
+    pub normal: ChaserTuning,
+    pub hard: ChaserTuning,
+    pub radius: f32,
+    /// How far ahead a chaser checks for obstacles before committing to a
+    /// direction.
+    pub look_ahead: f32,
+    /// Chasers start evenly spaced on a circle this far from the player's
+    /// spawn point.
+    pub spawn_distance: f32,
+}
+
+impl Default for ChaserConfig {
+    fn default() -> Self {
+        Self {
+            easy: ChaserTuning {
+                count: 1,
+                speed: 8.0,
+                detection_range: 30.0,
+            },
+            normal: ChaserTuning {
+                count: 2,
+                speed: 12.0,
+                detection_range: 45.0,
+            },
+            hard: ChaserTuning {
+                count: 4,
+                speed: 16.0,
+                detection_range: 70.0,
+            },
+            radius: 1.5,
+            look_ahead: 6.0,
+            spawn_distance: 80.0,
+        }
+    }
+}
+
+impl ChaserConfig {
+    pub fn tuning(&self, difficulty: Difficulty) -> &ChaserTuning {
+        match difficulty {
+            Difficulty::Easy => &self.easy,
+            Difficulty::Normal => &self.normal,
+            Difficulty::Hard => &self.hard,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct ChaserAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for ChaserAssets {
+    fn from_world(world: &mut World) -> Self {
+        let radius = world.resource::<ChaserConfig>().radius;
+        let mesh = world.resource_mut::<Assets<Mesh>>().add(Sphere { radius });
+        let material = world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial {
+                base_color: Color::srgb(0.8, 0.1, 0.1),
+                emissive: LinearRgba::rgb(2.0, 0.1, 0.1),
+                ..default()
+            });
+        Self { mesh, material }
+    }
+}
+
+#[derive(Component)]
+pub struct Chaser;
+
+/// Called from level setup, so chasers are torn down with the rest of the
+/// level.
+pub fn spawn_chasers(
+    commands: &mut Commands,
+    assets: &ChaserAssets,
+    config: &ChaserConfig,
+    settings: &Settings,
+    spawn_point: Vec3,
+) {
+    if !settings.chasers {
+        return;
+    }
+
+    let count = config.tuning(settings.difficulty).count;
+    for i in 0..count {
+        let angle = i as f32 / count as f32 * std::f32::consts::TAU;
+        let position = spawn_point.with_y(4.0)
+            + Vec3::new(angle.cos(), 0.0, angle.sin()) * config.spawn_distance;
+
+        // Kinematic sensors: they move only where steered and never shove
+        // the player, who loses on contact anyway.
+        commands.spawn((
+            Mesh3d(assets.mesh.clone()),
+            MeshMaterial3d(assets.material.clone()),
+            Transform::from_translation(position),
+            RigidBody::Kinematic,
+            Collider::sphere(config.radius),
+            Sensor,
+            Chaser,
+            LevelEntity,
+        ));
+    }
+}
+
+fn move_chasers(
+    spatial_query: SpatialQuery,
+    config: Res<ChaserConfig>,
+    settings: Res<Settings>,
+    run_timer: Res<RunTimer>,
+    player: Query<(Entity, &GlobalTransform), With<Player>>,
+    mut chasers: Query<(Entity, &GlobalTransform, &mut LinearVelocity), With<Chaser>>,
+) {
+    let tuning = config.tuning(settings.difficulty);
+    let player = player.single().ok();
+
+    let mut excluded: Vec<Entity> = chasers.iter().map(|(entity, ..)| entity).collect();
+    excluded.extend(player.map(|(entity, _)| entity));
+    let filter = SpatialQueryFilter::from_excluded_entities(excluded);
+    let shape = Collider::sphere(config.radius);
+
+    for (_, transform, mut velocity) in chasers.iter_mut() {
+        let origin = transform.translation();
+        let target = player
+            .filter(|_| run_timer.running)
+            .map(|(_, player)| (player.translation() - origin).with_y(0.0))
+            .filter(|offset| offset.length() <= tuning.detection_range);
+
+        let direction = target
+            .and_then(|offset| Dir3::new(offset).ok())
+            .and_then(|desired| {
+                steer(desired, |direction| {
+                    spatial_query
+                        .cast_shape(
+                            &shape,
+                            origin,
+                            Quat::IDENTITY,
+                            direction,
+                            &ShapeCastConfig::from_max_distance(config.look_ahead),
+                            &filter,
+                        )
+                        .is_none()
+                })
+            });
+
+        velocity.0 = direction.map_or(Vec3::ZERO, |direction| direction * tuning.speed);
+    }
+}
+
+/// The direction closest to `desired` that `is_clear`, fanning out to
+/// alternate sides. `None` when boxed in, so the chaser waits rather than
+/// pushing into an obstacle.
+fn steer(desired: Dir3, is_clear: impl Fn(Dir3) -> bool) -> Option<Dir3> {
+    std::iter::once(0.0)
+        .chain((1..=STEER_STEPS).flat_map(|step| {
+            let angle = step as f32 * STEER_STEP;
+            [angle, -angle]
+        }))
+        .map(|angle| Quat::from_rotation_y(angle) * desired)
+        .find(|direction| is_clear(*direction))
+}
+
+fn catch_player(
+    mut event_game_over: EventWriter<GameOver>,
+    mut lose_cause: ResMut<LoseCause>,
+    run_timer: Res<RunTimer>,
+    pending: Option<Res<PendingGameOver>>,
+    players: Query<&CollidingEntities, AnyPlayer>,
+    chasers: Query<(), With<Chaser>>,
+) {
+    if pending.is_some() || !run_timer.running {
+        return;
+    }
+
+    let caught = players
+        .iter()
+        .flat_map(|colliding| colliding.iter())
+        .any(|entity| chasers.contains(*entity));
+    if caught {
+        *lose_cause = LoseCause::Caught;
+        event_game_over.write(GameOver(GameState::Lose));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::scene::ScenePlugin;
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+
+    #[test]
+    fn steer_keeps_a_clear_straight_line() {
+        assert_eq!(steer(Dir3::X, |_| true), Some(Dir3::X));
+    }
+
+    #[test]
+    fn steer_turns_to_the_nearest_clear_side() {
+        // An obstacle dead ahead, wide enough to block the first step either
+        // side as well.
+        let blocked = |direction: Dir3| direction.angle_between(Vec3::X) < STEER_STEP * 1.5;
+        let steered = steer(Dir3::X, |direction| !blocked(direction)).unwrap();
+
+        let expected = Quat::from_rotation_y(2.0 * STEER_STEP) * Vec3::X;
+        assert!(steered.abs_diff_eq(expected, 1e-5), "{steered:?}");
+        // Still heading towards the player, just around the obstacle.
+        assert!(steered.dot(Vec3::X) > 0.0);
+    }
+
+    #[test]
+    fn steer_waits_when_boxed_in() {
+        assert_eq!(steer(Dir3::X, |_| false), None);
+    }
+
+    #[test]
+    fn chaser_goes_around_an_obstacle_to_the_player() {
+        let step = Duration::from_secs_f64(1.0 / 64.0);
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            TransformPlugin,
+            AssetPlugin::default(),
+            ScenePlugin,
+            PhysicsPlugins::default(),
+        ))
+        .init_asset::<Mesh>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(step))
+        .insert_resource(Time::<Fixed>::from_duration(step))
+        .init_resource::<ChaserConfig>()
+        .init_resource::<Settings>()
+        .insert_resource(RunTimer {
+            running: true,
+            ..default()
+        })
+        .add_systems(Update, move_chasers);
+        // Physics sets up some of its resources here, which `App::run`
+        // would do.
+        app.finish();
+        app.cleanup();
+
+        let player = Vec3::new(30.0, 4.0, 0.0);
+        app.world_mut().spawn((
+            Player,
+            Transform::from_translation(player),
+            RigidBody::Static,
+            Collider::sphere(0.5),
+        ));
+        // A wall square across the straight line from the chaser.
+        let wall = Cuboid::new(2.0, 8.0, 8.0);
+        let wall_center = Vec3::new(12.0, 4.0, 0.0);
+        app.world_mut().spawn((
+            Transform::from_translation(wall_center),
+            RigidBody::Static,
+            Collider::from(wall),
+        ));
+        let chaser = app
+            .world_mut()
+            .spawn((
+                Transform::from_xyz(0.0, 4.0, 0.0),
+                RigidBody::Kinematic,
+                Collider::sphere(ChaserConfig::default().radius),
+                Sensor,
+                Chaser,
+            ))
+            .id();
+
+        // Never overlapping the wall.
+        let radius = ChaserConfig::default().radius;
+        let overlaps_wall = |position: Vec3| {
+            let closest =
+                position.clamp(wall_center - wall.half_size, wall_center + wall.half_size);
+            position.distance(closest) < radius
+        };
+        for _ in 0..4 * 64 {
+            app.update();
+            let position = app.world().get::<Transform>(chaser).unwrap().translation;
+            assert!(
+                !overlaps_wall(position),
+                "went through the wall at {position}"
+            );
+        }
+
+        let position = app.world().get::<Transform>(chaser).unwrap().translation;
+        assert!(
+            position.x > wall_center.x,
+            "stuck behind the wall at {position}"
+        );
+        assert!(
+            position.distance(player) < 3.0,
+            "didn't reach the player, at {position}"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::countdown::RunStarted;
+use crate::hud::{ComboText, ShieldIcon};
+use crate::settings::{DifficultyConfig, Settings};
+use crate::{AtomCollected, AtomCollectionSet, GameState};
+
+/// Collecting atoms in quick succession builds a combo, which resets once the
+/// window lapses without another pickup. Each atom scores its value times the
+/// combo it was collected at.
+///
+/// With `Settings::combo_shields` on, every `shield_every` pickups in one
+/// combo earns a shield. A held shield is spent instead of resetting the
+/// combo, letting it survive one lapse.
+pub struct ComboPlugin;
+
+impl Plugin for ComboPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ComboConfig>()
+            .init_resource::<Combo>()
+            .add_systems(
+                Update,
+                (
+                    reset_combo.run_if(on_event::<RunStarted>),
+                    (shield_icon, combo_text).run_if(in_state(GameState::Game)),
+                )
+                    .chain(),
+            )
+            // On the fixed step with collection, so the window lasts the same
+            // at any framerate.
+            .add_systems(
+                FixedUpdate,
+                (count_combo, tick_combo)
+                    .chain()
+                    .after(AtomCollectionSet)
+                    .run_if(in_state(GameState::Game)),
+            );
+    }
+}
+
+#[derive(Resource)]
+pub struct ComboConfig {
+    /// Time allowed between pickups before the combo resets.
+    pub window: Duration,
+    /// Pickups within one combo needed to earn a shield.
+    pub shield_every: u32,
+    pub max_shields: u32,
+}
+
+impl Default for ComboConfig {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(2),
+            shield_every: 5,
+            max_shields: 1,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Combo {
+    pub count: u32,
+    pub timer: Timer,
+    /// The run's score so far.
+    pub score: u32,
+}
+
+/// Combo shields held by the player.
+#[derive(Component, Default)]
+pub struct ComboShield {
+    pub count: u32,
+}
+
+fn reset_combo(mut combo: ResMut<Combo>) {
+    *combo = Combo::default();
+}
+
+fn count_combo(
+    mut combo: ResMut<Combo>,
+    mut collected: EventReader<AtomCollected>,
+    mut shields: Query<&mut ComboShield>,
+    config: Res<ComboConfig>,
+    settings: Res<Settings>,
+) {
+    for event in collected.read() {
+        combo.count += 1;
+        combo.timer = Timer::new(config.window, TimerMode::Once);
+        combo.score += event.value * combo.count;
+
+        if !settings.combo_shields || !combo.count.is_multiple_of(config.shield_every.max(1)) {
+            continue;
+        }
+        for mut shield in shields.iter_mut() {
+            if shield.count < config.max_shields {
+                shield.count += 1;
+            }
+        }
+    }
+}
+
+fn tick_combo(
+    mut combo: ResMut<Combo>,
+    mut shields: Query<&mut ComboShield>,
+    config: Res<ComboConfig>,
+    settings: Res<Settings>,
+    difficulty: Res<DifficultyConfig>,
+    time: Res<Time>,
+) {
+    let delta = time
+        .delta()
+        .mul_f32(difficulty.tuning(settings.difficulty).timer_speed);
+    if combo.count == 0 || !combo.timer.tick(delta).just_finished() {
+        return;
+    }
+
+    if let Some(mut shield) = shields.iter_mut().find(|shield| shield.count > 0) {
+        shield.count -= 1;
+        combo.timer = Timer::new(config.window, TimerMode::Once);
+        return;
+    }
+
+    combo.count = 0;
+}
+
+fn shield_icon(
+    shields: Query<&ComboShield, Changed<ComboShield>>,
+    mut icons: Query<&mut Visibility, With<ShieldIcon>>,
+) {
+    let Some(shield) = shields.iter().next() else {
+        return;
+    };
+
+    let visibility = if shield.count > 0 {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    for mut icon in icons.iter_mut() {
+        icon.set_if_neq(visibility);
+    }
+}
+
+fn combo_text(combo: Res<Combo>, mut text_query: Query<&mut Text, With<ComboText>>) {
+    if !combo.is_changed() {
+        return;
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.0 = if combo.count > 1 {
+            format!("Combo x{}", combo.count)
+        } else {
+            String::new()
+        };
+    }
+}
@@ -0,0 +1,383 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::settings::Settings;
+use crate::{
+    CURSOR_LOCK_KEY, FontAssets, GameState, Menu, NORMAL_BUTTON, coop, debug_overlay, physics_debug,
+};
+
+/// Keys with fixed uses elsewhere, which no action can take over, and what
+/// they're for.
+const RESERVED_KEYS: [(KeyCode, &str); 8] = [
+    (CURSOR_LOCK_KEY, "the cursor lock"),
+    (debug_overlay::TOGGLE_KEY, "the debug overlay"),
+    (physics_debug::TOGGLE_KEY, "the physics debug view"),
+    (coop::FORWARD_KEY, "menus, keyboard look and player two"),
+    (coop::BACK_KEY, "menus, keyboard look and player two"),
+    (coop::TURN_LEFT_KEY, "keyboard look and player two"),
+    (coop::TURN_RIGHT_KEY, "keyboard look and player two"),
+    (coop::JUMP_KEY, "menus and player two"),
+];
+
+/// Keyboard bindings for the player's actions, and the screen used to
+/// change them.
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Rebinding>()
+            .add_systems(PreStartup, load_key_bindings)
+            .add_systems(
+                Update,
+                (
+                    store_key_bindings.run_if(resource_changed::<KeyBindings>),
+                    setup_controls_menu.run_if(in_state(GameState::Menu).and(run_once)),
+                    controls_button_system
+                        .run_if(in_state(GameState::Menu).or(in_state(GameState::Controls))),
+                    (capture_rebind, binding_labels)
+                        .chain()
+                        .run_if(in_state(GameState::Controls)),
+                ),
+            )
+            .add_systems(OnExit(GameState::Controls), cancel_rebind);
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Action {
+    Forward,
+    Back,
+    Left,
+    Right,
+    Jump,
+    Sprint,
+    Dash,
+    Rewind,
+    Pause,
+}
+
+impl Action {
+    pub const ALL: [Action; 9] = [
+        Action::Forward,
+        Action::Back,
+        Action::Left,
+        Action::Right,
+        Action::Jump,
+        Action::Sprint,
+        Action::Dash,
+        Action::Rewind,
+        Action::Pause,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Action::Forward => "Forward",
+            Action::Back => "Back",
+            Action::Left => "Left",
+            Action::Right => "Right",
+            Action::Jump => "Jump",
+            Action::Sprint => "Sprint",
+            Action::Dash => "Dash",
+            Action::Rewind => "Rewind",
+            Action::Pause => "Pause",
+        }
+    }
+}
+
+#[derive(Resource, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub forward: KeyCode,
+    pub back: KeyCode,
+    pub left: KeyCode,
+    pub right: KeyCode,
+    pub jump: KeyCode,
+    pub sprint: KeyCode,
+    pub dash: KeyCode,
+    pub rewind: KeyCode,
+    pub pause: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            forward: KeyCode::KeyW,
+            back: KeyCode::KeyS,
+            left: KeyCode::KeyA,
+            right: KeyCode::KeyD,
+            jump: KeyCode::Space,
+            sprint: KeyCode::ShiftLeft,
+            dash: KeyCode::ControlLeft,
+            rewind: KeyCode::KeyR,
+            pause: KeyCode::Escape,
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn get(&self, action: Action) -> KeyCode {
+        match action {
+            Action::Forward => self.forward,
+            Action::Back => self.back,
+            Action::Left => self.left,
+            Action::Right => self.right,
+            Action::Jump => self.jump,
+            Action::Sprint => self.sprint,
+            Action::Dash => self.dash,
+            Action::Rewind => self.rewind,
+            Action::Pause => self.pause,
+        }
+    }
+
+    /// Binds `key` to `action`, unless the key is reserved or another
+    /// action already uses it, in which case nothing changes.
+    pub fn set(&mut self, action: Action, key: KeyCode) -> Result<(), BindError> {
+        if let Some((_, used_for)) = RESERVED_KEYS.iter().find(|(reserved, _)| *reserved == key) {
+            return Err(BindError::Reserved(used_for));
+        }
+        if let Some(other) = Action::ALL
+            .into_iter()
+            .find(|other| *other != action && self.get(*other) == key)
+        {
+            return Err(BindError::Taken(other));
+        }
+
+        let slot = match action {
+            Action::Forward => &mut self.forward,
+            Action::Back => &mut self.back,
+            Action::Left => &mut self.left,
+            Action::Right => &mut self.right,
+            Action::Jump => &mut self.jump,
+            Action::Sprint => &mut self.sprint,
+            Action::Dash => &mut self.dash,
+            Action::Rewind => &mut self.rewind,
+            Action::Pause => &mut self.pause,
+        };
+        *slot = key;
+        Ok(())
+    }
+}
+
+/// Why a key couldn't be bound.
+#[derive(Debug)]
+pub enum BindError {
+    /// Another action already uses it.
+    Taken(Action),
+    /// It has a fixed use, described here.
+    Reserved(&'static str),
+}
+
+fn load_key_bindings(mut commands: Commands, settings: Res<Settings>) {
+    commands.insert_resource(settings.key_bindings.clone());
+}
+
+fn store_key_bindings(bindings: Res<KeyBindings>, mut settings: ResMut<Settings>) {
+    if settings.key_bindings != *bindings {
+        settings.key_bindings = bindings.clone();
+    }
+}
+
+/// The action waiting for a key press, and the last message to show.
+#[derive(Resource, Default)]
+pub struct Rebinding {
+    action: Option<Action>,
+    message: String,
+}
+
+#[derive(Component, Clone, Copy)]
+pub enum ControlsMenuAction {
+    Open,
+    Rebind(Action),
+    Back,
+}
+
+#[derive(Component)]
+struct BindingLabel(Action);
+
+#[derive(Component)]
+struct RebindMessage;
+
+pub fn key_name(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+    name.strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+        .unwrap_or(&name)
+        .to_string()
+}
+
+fn controls_button_system(
+    interaction_query: Query<
+        (&Interaction, &ControlsMenuAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut rebinding: ResMut<Rebinding>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, action) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match *action {
+            ControlsMenuAction::Open => next_state.set(GameState::Controls),
+            ControlsMenuAction::Rebind(action) => {
+                rebinding.action = Some(action);
+                rebinding.message = format!("Press a key for {}", action.label());
+            }
+            ControlsMenuAction::Back => next_state.set(GameState::Menu),
+        }
+    }
+}
+
+fn capture_rebind(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut bindings: ResMut<KeyBindings>,
+    mut rebinding: ResMut<Rebinding>,
+) {
+    let Some(action) = rebinding.action else {
+        return;
+    };
+    // Started this frame, maybe by Enter on a focused button, which isn't
+    // meant as the new key.
+    if rebinding.is_changed() {
+        return;
+    }
+    let Some(key) = keyboard.get_just_pressed().next().copied() else {
+        return;
+    };
+
+    rebinding.message = match bindings.set(action, key) {
+        Ok(()) => {
+            rebinding.action = None;
+            String::new()
+        }
+        Err(BindError::Taken(other)) => {
+            format!("{} is already bound to {}", key_name(key), other.label())
+        }
+        Err(BindError::Reserved(used_for)) => {
+            format!("{} is reserved for {used_for}", key_name(key))
+        }
+    };
+}
+
+/// Run condition for while the controls screen is waiting for a key, so
+/// other keyboard handling can stand aside.
+pub fn awaiting_key(rebinding: Res<Rebinding>) -> bool {
+    rebinding.action.is_some()
+}
+
+fn cancel_rebind(mut rebinding: ResMut<Rebinding>) {
+    *rebinding = Rebinding::default();
+}
+
+fn binding_labels(
+    bindings: Res<KeyBindings>,
+    rebinding: Res<Rebinding>,
+    mut labels: Query<(&mut Text, &BindingLabel)>,
+    mut messages: Query<&mut Text, (With<RebindMessage>, Without<BindingLabel>)>,
+) {
+    if !bindings.is_changed() && !rebinding.is_changed() {
+        return;
+    }
+
+    for (mut text, label) in labels.iter_mut() {
+        let key = if rebinding.action == Some(label.0) {
+            "...".to_string()
+        } else {
+            key_name(bindings.get(label.0))
+        };
+        text.0 = format!("{}: {key}", label.0.label());
+    }
+
+    for mut text in messages.iter_mut() {
+        text.0.clone_from(&rebinding.message);
+    }
+}
+
+fn setup_controls_menu(mut commands: Commands, font_assets: Res<FontAssets>) {
+    commands
+        .spawn((
+            Menu {
+                show_state: GameState::Controls,
+            },
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            Visibility::Hidden,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Controls"),
+                TextFont {
+                    font: font_assets.u_atom.clone(),
+                    font_size: 80.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextShadow::default(),
+            ));
+
+            for action in Action::ALL {
+                parent.spawn(controls_button(
+                    ControlsMenuAction::Rebind(action),
+                    (BindingLabel(action), Text::new(action.label())),
+                    &font_assets,
+                ));
+            }
+
+            parent.spawn((
+                RebindMessage,
+                Text::default(),
+                TextFont {
+                    font: font_assets.u_atom.clone(),
+                    font_size: 28.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextShadow::default(),
+            ));
+
+            parent.spawn(controls_button(
+                ControlsMenuAction::Back,
+                Text::new("Back"),
+                &font_assets,
+            ));
+        });
+}
+
+fn controls_button(
+    action: ControlsMenuAction,
+    label: impl Bundle,
+    assets: &FontAssets,
+) -> impl Bundle {
+    (
+        Button,
+        action,
+        Node {
+            width: Val::Px(300.0),
+            height: Val::Px(60.0),
+            border: UiRect::all(Val::Px(5.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BorderColor(Color::BLACK),
+        BorderRadius::MAX,
+        BackgroundColor(NORMAL_BUTTON),
+        children![(
+            label,
+            TextFont {
+                font: assets.u_atom.clone(),
+                font_size: 32.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+            TextShadow::default(),
+        )],
+    )
+}
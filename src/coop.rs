@@ -0,0 +1,220 @@
+use bevy::core_pipeline::Skybox;
+use bevy::prelude::*;
+use bevy::render::camera::Viewport;
+use bevy::render::view::RenderLayers;
+use bevy::window::PrimaryWindow;
+use bevy_third_person_camera::ThirdPersonCamera;
+
+use crate::{CameraConfig, GameState, Player};
+
+/// Player two's keys. Left and right turn their camera rather than strafe,
+/// since there are no keys to spare for looking around.
+pub const FORWARD_KEY: KeyCode = KeyCode::ArrowUp;
+pub const BACK_KEY: KeyCode = KeyCode::ArrowDown;
+pub const TURN_LEFT_KEY: KeyCode = KeyCode::ArrowLeft;
+pub const TURN_RIGHT_KEY: KeyCode = KeyCode::ArrowRight;
+pub const JUMP_KEY: KeyCode = KeyCode::Enter;
+
+/// Local two-player mode. With `Players` at 2, each run spawns a second
+/// ball beside the first and the screen splits down the middle, player one
+/// on the left. Atoms either player collects count towards the same
+/// objective, and hazard hits come off one shared health bar.
+///
+/// The camera plugin only drives a single camera, so player two's is a
+/// plain follow camera turned with the arrow keys.
+pub struct CoopPlugin;
+
+impl Plugin for CoopPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Players>()
+            .init_resource::<CoopConfig>()
+            .add_systems(
+                Update,
+                (
+                    spawn_coop_camera.run_if(
+                        any_with_component::<SecondPlayer>
+                            .and(not(any_with_component::<CoopCamera>)),
+                    ),
+                    despawn_coop_camera.run_if(
+                        not(any_with_component::<SecondPlayer>)
+                            .and(any_with_component::<CoopCamera>),
+                    ),
+                    (turn_coop_camera, follow_second_player)
+                        .chain()
+                        .run_if(in_state(GameState::Game)),
+                    split_screen,
+                ),
+            );
+    }
+}
+
+/// How many play, 1 or 2. Read when a run starts, so a change from the
+/// pause menu applies to the next one.
+#[derive(Resource, PartialEq)]
+pub struct Players(pub u32);
+
+impl Default for Players {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+impl Players {
+    pub fn toggle(&mut self) {
+        self.0 = if self.0 == 2 { 1 } else { 2 };
+    }
+}
+
+#[derive(Resource)]
+pub struct CoopConfig {
+    pub color: Color,
+    /// Where player two starts, from player one's spawn point.
+    pub spawn_offset: Vec3,
+    /// Distance from player two's camera to the ball.
+    pub camera_distance: f32,
+    /// How far player two's camera looks down, in radians.
+    pub camera_pitch: f32,
+    /// Radians per second the camera turns with the arrow keys held.
+    pub turn_speed: f32,
+}
+
+impl Default for CoopConfig {
+    fn default() -> Self {
+        Self {
+            color: Color::srgb(0.2, 0.7, 0.3),
+            spawn_offset: Vec3::new(0.0, 0.0, 3.0),
+            camera_distance: 8.0,
+            camera_pitch: 0.35,
+            turn_speed: 2.0,
+        }
+    }
+}
+
+/// Player two's ball. It isn't a `Player`, which many systems expect only
+/// one of.
+#[derive(Component)]
+pub struct SecondPlayer;
+
+/// Either player's ball.
+pub type AnyPlayer = Or<(With<Player>, With<SecondPlayer>)>;
+
+/// Player two's camera, drawn on the right half of the screen.
+#[derive(Component)]
+pub struct CoopCamera {
+    /// Heading around the vertical axis, in radians.
+    yaw: f32,
+}
+
+fn spawn_coop_camera(
+    mut commands: Commands,
+    camera_config: Res<CameraConfig>,
+    player_camera: Single<(&Camera, Option<&Skybox>), With<ThirdPersonCamera>>,
+    second_player: Single<&Transform, With<SecondPlayer>>,
+) {
+    let (camera, skybox) = player_camera.into_inner();
+    // Starts behind the ball, facing the way it was spawned.
+    let forward = second_player.forward();
+    let mut coop_camera = commands.spawn((
+        CoopCamera {
+            yaw: f32::atan2(-forward.x, -forward.z),
+        },
+        Camera {
+            // Drawn before player one's, so overlays that follow the
+            // topmost camera stay on theirs.
+            order: -1,
+            clear_color: camera.clear_color,
+            ..default()
+        },
+        Camera3d::default(),
+        camera_config.projection(),
+        RenderLayers::layer(0),
+        Transform::from_translation(second_player.translation),
+    ));
+    if let Some(skybox) = skybox {
+        coop_camera.insert(skybox.clone());
+    }
+}
+
+fn despawn_coop_camera(mut commands: Commands, camera: Single<Entity, With<CoopCamera>>) {
+    commands.entity(*camera).despawn();
+}
+
+fn turn_coop_camera(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    config: Res<CoopConfig>,
+    time: Res<Time>,
+    mut camera: Single<&mut CoopCamera>,
+) {
+    let turn = match (
+        keyboard.pressed(TURN_LEFT_KEY),
+        keyboard.pressed(TURN_RIGHT_KEY),
+    ) {
+        (true, false) => 1.0,
+        (false, true) => -1.0,
+        _ => 0.0,
+    };
+    camera.yaw += turn * config.turn_speed * time.delta_secs();
+}
+
+fn follow_second_player(
+    config: Res<CoopConfig>,
+    second_player: Single<&Transform, (With<SecondPlayer>, Without<CoopCamera>)>,
+    camera: Single<(&CoopCamera, &mut Transform)>,
+) {
+    let (coop_camera, mut transform) = camera.into_inner();
+    let rotation = Quat::from_euler(EulerRot::YXZ, coop_camera.yaw, -config.camera_pitch, 0.0);
+    transform.translation =
+        second_player.translation + rotation * Vec3::new(0.0, 0.0, config.camera_distance);
+    transform.rotation = rotation;
+}
+
+/// Halves the window between the two cameras during play. Elsewhere, like
+/// behind the pause menu, player one's camera has the whole window.
+fn split_screen(
+    state: Res<State<GameState>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut player_camera: Single<&mut Camera, (With<ThirdPersonCamera>, Without<CoopCamera>)>,
+    mut coop_camera: Query<&mut Camera, With<CoopCamera>>,
+) {
+    let split = *state.get() == GameState::Game;
+    let Ok(mut coop_camera) = coop_camera.single_mut() else {
+        if player_camera.viewport.is_some() {
+            player_camera.viewport = None;
+        }
+        return;
+    };
+
+    if coop_camera.is_active != split {
+        coop_camera.is_active = split;
+    }
+    if !split {
+        if player_camera.viewport.is_some() {
+            player_camera.viewport = None;
+        }
+        return;
+    }
+
+    let size = window.physical_size();
+    let half = UVec2::new(size.x / 2, size.y);
+    // Nothing to split while minimised.
+    if half.x == 0 || half.y == 0 {
+        return;
+    }
+    set_viewport(&mut player_camera, UVec2::ZERO, half);
+    set_viewport(&mut coop_camera, UVec2::new(half.x, 0), half);
+}
+
+// Only written when it moves, so the camera isn't marked changed every frame.
+fn set_viewport(camera: &mut Mut<Camera>, position: UVec2, size: UVec2) {
+    let current = camera
+        .viewport
+        .as_ref()
+        .map(|viewport| (viewport.physical_position, viewport.physical_size));
+    if current != Some((position, size)) {
+        camera.viewport = Some(Viewport {
+            physical_position: position,
+            physical_size: size,
+            ..default()
+        });
+    }
+}
@@ -0,0 +1,295 @@
+use std::time::Duration;
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_third_person_camera::ThirdPersonCamera;
+use bevy_tnua::TnuaToggle;
+
+use crate::GameState;
+use crate::coop::AnyPlayer;
+use crate::hud::CountdownText;
+use crate::levels::{Levels, SelectedLevel};
+use crate::stats::UnsavedPlayTime;
+
+const GO_DISPLAY_SECS: f32 = 1.0;
+
+/// Runs a "3-2-1-Go" countdown at the start of each run. Movement input and
+/// camera control stay locked and the ball is frozen in place until "Go",
+/// which is also when the run timer starts, so recorded times are comparable
+/// between runs.
+pub struct CountdownPlugin;
+
+impl Plugin for CountdownPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<RunStarted>()
+            .init_resource::<CountdownConfig>()
+            .init_resource::<Countdown>()
+            .init_resource::<RunTimer>()
+            .add_systems(
+                OnTransition {
+                    exited: GameState::LevelSelect,
+                    entered: GameState::Game,
+                },
+                start_countdown,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Win,
+                    entered: GameState::Game,
+                },
+                start_countdown,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Lose,
+                    entered: GameState::Game,
+                },
+                start_countdown,
+            )
+            .add_systems(
+                Update,
+                (
+                    tick_countdown,
+                    freeze_player,
+                    tick_run_timer,
+                    countdown_text,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Game)),
+            )
+            .add_systems(OnEnter(GameState::Win), run_time_text);
+    }
+}
+
+#[derive(Resource)]
+pub struct CountdownConfig {
+    /// Countdown length in whole seconds. Zero starts the run immediately.
+    /// A level can set its own with `LevelDefinition::countdown`.
+    pub seconds: u32,
+}
+
+impl Default for CountdownConfig {
+    fn default() -> Self {
+        Self { seconds: 3 }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Countdown {
+    timer: Timer,
+}
+
+impl Countdown {
+    pub fn finished(&self) -> bool {
+        self.timer.finished()
+    }
+}
+
+/// Sent whenever a fresh run begins, so per-run state can reset itself.
+#[derive(Event)]
+pub struct RunStarted;
+
+/// Marks a player held still by the countdown.
+#[derive(Component)]
+struct Frozen;
+
+/// Win screen line showing how long the run took.
+#[derive(Component)]
+pub struct RunTimeText;
+
+/// Time since "Go". Only ticks in `GameState::Game`, so pausing stops it.
+#[derive(Resource, Default)]
+pub struct RunTimer {
+    pub elapsed: Duration,
+    pub running: bool,
+}
+
+fn start_countdown(
+    mut countdown: ResMut<Countdown>,
+    mut run_timer: ResMut<RunTimer>,
+    mut run_started: EventWriter<RunStarted>,
+    config: Res<CountdownConfig>,
+    levels: Res<Levels>,
+    selected: Res<SelectedLevel>,
+) {
+    let seconds = selected.get(&levels).countdown.unwrap_or(config.seconds);
+    countdown.timer = Timer::new(Duration::from_secs(seconds.into()), TimerMode::Once);
+    *run_timer = RunTimer::default();
+    run_started.write(RunStarted);
+}
+
+fn tick_countdown(
+    mut countdown: ResMut<Countdown>,
+    mut run_timer: ResMut<RunTimer>,
+    mut cam_query: Query<&mut ThirdPersonCamera>,
+    time: Res<Time>,
+) {
+    if countdown.finished() && run_timer.running {
+        return;
+    }
+
+    countdown.timer.tick(time.delta());
+
+    let Ok(mut cam) = cam_query.single_mut() else {
+        return;
+    };
+
+    if countdown.finished() {
+        // Input, camera control and the run timer all unlock on this frame.
+        cam.cursor_lock_active = true;
+        run_timer.running = true;
+        run_timer.elapsed = Duration::ZERO;
+    } else if cam.cursor_lock_active {
+        cam.cursor_lock_active = false;
+    }
+}
+
+fn freeze_player(
+    mut commands: Commands,
+    countdown: Res<Countdown>,
+    mut players: Query<
+        (
+            Entity,
+            Has<Frozen>,
+            &mut LinearVelocity,
+            &mut AngularVelocity,
+        ),
+        AnyPlayer,
+    >,
+) {
+    for (player, frozen, mut linear, mut angular) in players.iter_mut() {
+        if !countdown.finished() && !frozen {
+            // Same approach as a rewind: a still kinematic body that Tnua
+            // leaves alone.
+            linear.0 = Vec3::ZERO;
+            angular.0 = Vec3::ZERO;
+            commands
+                .entity(player)
+                .insert((Frozen, RigidBody::Kinematic, TnuaToggle::Disabled));
+        } else if countdown.finished() && frozen {
+            commands
+                .entity(player)
+                .remove::<Frozen>()
+                .insert((RigidBody::Dynamic, TnuaToggle::Enabled));
+        }
+    }
+}
+
+fn tick_run_timer(
+    mut run_timer: ResMut<RunTimer>,
+    mut play_time: ResMut<UnsavedPlayTime>,
+    time: Res<Time>,
+    countdown: Res<Countdown>,
+) {
+    // The countdown just finished on this frame and already zeroed the timer.
+    if run_timer.running && !countdown.is_changed() {
+        run_timer.elapsed += time.delta();
+        play_time.0 += time.delta();
+    }
+}
+
+fn countdown_text(
+    countdown: Res<Countdown>,
+    run_timer: Res<RunTimer>,
+    mut text_query: Query<&mut Text, With<CountdownText>>,
+) {
+    let label = if !countdown.finished() {
+        countdown.timer.remaining_secs().ceil().to_string()
+    } else if run_timer.elapsed.as_secs_f32() < GO_DISPLAY_SECS
+        && countdown.timer.duration() > Duration::ZERO
+    {
+        "Go!".to_string()
+    } else {
+        String::new()
+    };
+
+    for mut text in text_query.iter_mut() {
+        if text.0 != label {
+            text.0 = label.clone();
+        }
+    }
+}
+
+fn run_time_text(run_timer: Res<RunTimer>, mut text_query: Query<&mut Text, With<RunTimeText>>) {
+    for mut text in text_query.iter_mut() {
+        text.0 = format!("Time: {:.2}s", run_timer.elapsed.as_secs_f32());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+    use crate::Player;
+    use crate::levels::{AtomLayout, LevelDefinition};
+
+    const STEP: Duration = Duration::from_millis(100);
+
+    #[test]
+    fn locked_until_go_and_timed_from_it() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(STEP))
+            .insert_resource(Levels(vec![LevelDefinition {
+                name: "Test".to_string(),
+                index: None,
+                ground_size: 100.0,
+                atoms: AtomLayout::Points(Vec::new()),
+                ramps: Vec::new(),
+                countdown: Some(1),
+                hazard_fraction: 0.0,
+            }]))
+            .init_resource::<SelectedLevel>()
+            .init_resource::<UnsavedPlayTime>()
+            .insert_state(GameState::LevelSelect)
+            .add_plugins(CountdownPlugin);
+
+        let camera = app.world_mut().spawn(ThirdPersonCamera::default()).id();
+        let player = app
+            .world_mut()
+            .spawn((Player, LinearVelocity(Vec3::X), AngularVelocity::default()))
+            .id();
+        app.update();
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Game);
+
+        // The level's one second, in tenths.
+        for _ in 0..9 {
+            app.update();
+            let world = app.world();
+            assert!(!world.resource::<Countdown>().finished());
+            let run_timer = world.resource::<RunTimer>();
+            assert!(!run_timer.running);
+            assert_eq!(run_timer.elapsed, Duration::ZERO);
+            assert!(
+                !world
+                    .get::<ThirdPersonCamera>(camera)
+                    .unwrap()
+                    .cursor_lock_active
+            );
+            assert_eq!(world.get::<TnuaToggle>(player), Some(&TnuaToggle::Disabled));
+            assert_eq!(world.get::<LinearVelocity>(player).unwrap().0, Vec3::ZERO);
+        }
+
+        // Go: everything unlocks with nothing on the clock yet.
+        app.update();
+        let world = app.world();
+        assert!(world.resource::<Countdown>().finished());
+        let run_timer = world.resource::<RunTimer>();
+        assert!(run_timer.running);
+        assert_eq!(run_timer.elapsed, Duration::ZERO);
+        assert!(
+            world
+                .get::<ThirdPersonCamera>(camera)
+                .unwrap()
+                .cursor_lock_active
+        );
+        assert_eq!(world.get::<TnuaToggle>(player), Some(&TnuaToggle::Enabled));
+
+        app.update();
+        assert_eq!(app.world().resource::<RunTimer>().elapsed, STEP);
+    }
+}
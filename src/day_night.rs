@@ -0,0 +1,141 @@
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+use bevy::core_pipeline::Skybox;
+use bevy::{color::palettes::css, prelude::*};
+use bevy_third_person_camera::ThirdPersonCamera;
+
+use crate::GameState;
+use crate::coop::CoopCamera;
+use crate::countdown::RunStarted;
+use crate::settings::Settings;
+use crate::sky::SkyConfig;
+
+/// Swings the sun across the sky over the course of a run, dimming the light
+/// and the skybox towards night. Every run starts at the same time of
+/// day and the clock only moves during play, so a seeded level looks the same
+/// at the same point in every run. With `Settings::day_night` off it stays
+/// noon.
+pub struct DayNightPlugin;
+
+impl Plugin for DayNightPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TimeOfDay>().add_systems(
+            Update,
+            (
+                reset_time_of_day.run_if(on_event::<RunStarted>),
+                advance_time_of_day,
+                update_sun,
+            )
+                .chain()
+                .run_if(in_state(GameState::Game)),
+        );
+    }
+}
+
+#[derive(Resource)]
+pub struct TimeOfDay {
+    /// Length of a full day and night.
+    pub period: Duration,
+    /// Time of day at the start of each run, as a fraction of the period.
+    /// Noon is 0.5.
+    pub start: f32,
+    pub day_illuminance: f32,
+    pub night_illuminance: f32,
+    pub day_color: Color,
+    pub night_color: Color,
+    /// Play time since the run started.
+    elapsed: Duration,
+}
+
+impl Default for TimeOfDay {
+    fn default() -> Self {
+        Self {
+            period: Duration::from_secs(240),
+            start: 0.35,
+            day_illuminance: 4000.0,
+            night_illuminance: 400.0,
+            day_color: css::DARK_GRAY.into(),
+            night_color: Color::srgb(0.05, 0.05, 0.12),
+            elapsed: Duration::ZERO,
+        }
+    }
+}
+
+impl TimeOfDay {
+    /// Where in the day the run is, from 0 (midnight) to 1.
+    pub fn fraction(&self) -> f32 {
+        let days = self.elapsed.as_secs_f32() / self.period.as_secs_f32().max(f32::EPSILON);
+        (self.start + days).rem_euclid(1.0)
+    }
+}
+
+/// The light moved and dimmed by the cycle.
+#[derive(Component)]
+pub struct Sun;
+
+fn reset_time_of_day(mut time_of_day: ResMut<TimeOfDay>) {
+    time_of_day.elapsed = Duration::ZERO;
+}
+
+fn advance_time_of_day(mut time_of_day: ResMut<TimeOfDay>, time: Res<Time>) {
+    time_of_day.elapsed += time.delta();
+}
+
+fn update_sun(
+    time_of_day: Res<TimeOfDay>,
+    settings: Res<Settings>,
+    sky_config: Res<SkyConfig>,
+    mut sun: Query<(&mut DirectionalLight, &mut Transform), With<Sun>>,
+    mut camera: Query<
+        (&mut Camera, Option<&mut Skybox>),
+        Or<(With<ThirdPersonCamera>, With<CoopCamera>)>,
+    >,
+) {
+    let fraction = if settings.day_night {
+        time_of_day.fraction()
+    } else {
+        0.5
+    };
+    // Angle from straight overhead, so 1 at noon and -1 at midnight.
+    let angle = (fraction - 0.5) * TAU;
+    let daylight = angle.cos();
+
+    // Past the horizon the light flips over and becomes a dim moon, so it
+    // never shines up through the ground.
+    let angle = if daylight < 0.0 {
+        angle + TAU / 2.0
+    } else {
+        angle
+    };
+    let direction = Vec3::new(angle.sin(), -angle.cos(), 0.0);
+    let brightness = daylight.max(0.0);
+
+    for (mut light, mut transform) in sun.iter_mut() {
+        let illuminance = time_of_day
+            .night_illuminance
+            .lerp(time_of_day.day_illuminance, brightness);
+        if light.illuminance != illuminance {
+            light.illuminance = illuminance;
+        }
+        transform.set_if_neq(Transform::default().looking_to(direction, Vec3::Z));
+    }
+
+    let sky = time_of_day
+        .night_color
+        .mix(&time_of_day.day_color, brightness);
+    // The clear colour only shows if the skybox isn't drawn.
+    let sky_brightness = sky_config
+        .night_brightness
+        .lerp(sky_config.day_brightness, brightness);
+    for (mut camera, skybox) in camera.iter_mut() {
+        if !matches!(camera.clear_color, ClearColorConfig::Custom(color) if color == sky) {
+            camera.clear_color = ClearColorConfig::Custom(sky);
+        }
+        if let Some(mut skybox) = skybox
+            && skybox.brightness != sky_brightness
+        {
+            skybox.brightness = sky_brightness;
+        }
+    }
+}
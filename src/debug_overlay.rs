@@ -0,0 +1,121 @@
+use bevy::diagnostic::{DiagnosticPath, DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+
+use crate::FontAssets;
+
+pub const TOGGLE_KEY: KeyCode = KeyCode::F3;
+
+/// A frame rate and frame time readout in the top right corner, toggled with
+/// F3. Hidden by default.
+pub struct DebugOverlayPlugin;
+
+impl Plugin for DebugOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugOverlay>().add_systems(
+            Update,
+            (
+                toggle_overlay,
+                setup_overlay
+                    .run_if(resource_exists::<FontAssets>.and(not(any_with_component::<Overlay>))),
+                (follow_active_camera, overlay_text).run_if(overlay_visible),
+                show_overlay,
+            )
+                .chain(),
+        );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct DebugOverlay {
+    pub visible: bool,
+}
+
+#[derive(Component)]
+struct Overlay;
+
+#[derive(Component)]
+struct OverlayText;
+
+fn overlay_visible(overlay: Res<DebugOverlay>) -> bool {
+    overlay.visible
+}
+
+fn toggle_overlay(keyboard: Res<ButtonInput<KeyCode>>, mut overlay: ResMut<DebugOverlay>) {
+    if keyboard.just_pressed(TOGGLE_KEY) {
+        overlay.visible = !overlay.visible;
+    }
+}
+
+fn setup_overlay(mut commands: Commands, font_assets: Res<FontAssets>) {
+    commands.spawn((
+        Overlay,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            right: Val::Px(10.0),
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        },
+        GlobalZIndex(20),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+        Visibility::Hidden,
+        children![(
+            OverlayText,
+            Text::default(),
+            TextFont {
+                font: font_assets.u_atom.clone(),
+                font_size: 20.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+        )],
+    ));
+}
+
+/// Which camera is drawing changes with the state (menu, game, intro,
+/// benchmark), so the overlay moves to the topmost active one.
+fn follow_active_camera(
+    mut commands: Commands,
+    overlay: Single<(Entity, Option<&UiTargetCamera>), With<Overlay>>,
+    cameras: Query<(Entity, &Camera)>,
+) {
+    let Some((camera, _)) = cameras
+        .iter()
+        .filter(|(_, camera)| camera.is_active)
+        .max_by_key(|(_, camera)| camera.order)
+    else {
+        return;
+    };
+
+    let (entity, target) = *overlay;
+    if target.is_none_or(|target| target.entity() != camera) {
+        commands.entity(entity).insert(UiTargetCamera(camera));
+    }
+}
+
+fn overlay_text(
+    diagnostics: Res<DiagnosticsStore>,
+    mut text: Single<&mut Text, With<OverlayText>>,
+) {
+    let smoothed = |path: &DiagnosticPath| {
+        diagnostics
+            .get(path)
+            .and_then(|diagnostic| diagnostic.smoothed())
+            .unwrap_or(0.0)
+    };
+    let fps = smoothed(&FrameTimeDiagnosticsPlugin::FPS);
+    let frame_time = smoothed(&FrameTimeDiagnosticsPlugin::FRAME_TIME);
+
+    text.0 = format!("{fps:.0} FPS\n{frame_time:.2} ms");
+}
+
+fn show_overlay(
+    overlay: Res<DebugOverlay>,
+    mut visibility: Single<&mut Visibility, With<Overlay>>,
+) {
+    visibility.set_if_neq(if overlay.visible {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    });
+}
@@ -0,0 +1,278 @@
+use std::time::Duration;
+
+use bevy::{
+    prelude::*,
+    window::{Monitor, MonitorSelection, PrimaryWindow, WindowMode, WindowMoved, WindowResized},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::settings::Settings;
+
+/// Resolutions offered for windowed mode.
+pub const RESOLUTIONS: [UVec2; 4] = [
+    UVec2::new(1280, 720),
+    UVec2::new(1600, 900),
+    UVec2::new(1920, 1080),
+    UVec2::new(2560, 1440),
+];
+
+/// Smallest window size worth remembering. Minimizing reports a size of
+/// zero on some platforms, which would reopen the window invisible.
+const MIN_WINDOW_SIZE: UVec2 = UVec2::new(320, 180);
+
+/// How long the window has to stay put before its new placement is saved, so
+/// dragging it doesn't rewrite the settings file every frame.
+const PLACEMENT_SETTLE: Duration = Duration::from_millis(500);
+
+/// Applies the chosen window mode, monitor and resolution to the primary
+/// window at startup and whenever they change. In windowed mode the window's
+/// position and size are remembered between runs. If the chosen monitor is
+/// unplugged, the window stays on whichever it's on until it's back.
+pub struct DisplayPlugin;
+
+impl Plugin for DisplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreStartup, load_display_config)
+            .add_systems(
+                Update,
+                (
+                    track_window_placement,
+                    apply_display_config.run_if(
+                        resource_changed::<DisplayConfig>
+                            .or(any_component_removed::<Monitor>)
+                            .or(|added: Query<(), Added<Monitor>>| !added.is_empty()),
+                    ),
+                    store_display_config.run_if(resource_changed::<DisplayConfig>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum DisplayMode {
+    Fullscreen,
+    Windowed,
+}
+
+/// Identifies a monitor from one run to the next, which neither the system's
+/// order for them nor their entities do once monitors are replugged.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct MonitorId {
+    pub name: Option<String>,
+    /// Top left corner on the desktop, in physical pixels. Tells identical
+    /// monitors apart.
+    pub position: IVec2,
+}
+
+impl MonitorId {
+    fn of(monitor: &Monitor) -> Self {
+        Self {
+            name: monitor.name.clone(),
+            position: monitor.physical_position,
+        }
+    }
+
+    /// Where this monitor is in `plugged_in`, if it's there. Falls back to a
+    /// name match, in case the desktop has been rearranged since.
+    pub fn find(&self, plugged_in: &[(Entity, MonitorId)]) -> Option<usize> {
+        plugged_in
+            .iter()
+            .position(|(_, id)| id == self)
+            .or_else(|| {
+                plugged_in
+                    .iter()
+                    .position(|(_, id)| self.name.is_some() && id.name == self.name)
+            })
+    }
+}
+
+/// The plugged in monitors from left to right, then top to bottom, which is
+/// the order the settings screen steps through them in.
+pub fn plugged_in<'a>(
+    monitors: impl IntoIterator<Item = (Entity, &'a Monitor)>,
+) -> Vec<(Entity, MonitorId)> {
+    let mut plugged_in: Vec<_> = monitors
+        .into_iter()
+        .map(|(entity, monitor)| (entity, MonitorId::of(monitor)))
+        .collect();
+    plugged_in.sort_by_key(|(_, id)| (id.position.x, id.position.y));
+    plugged_in
+}
+
+#[derive(Resource, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+    pub mode: DisplayMode,
+    /// The monitor to open on, or `None` for whichever the window is on.
+    pub monitor_id: Option<MonitorId>,
+    /// Window size in windowed mode. Fullscreen always uses the monitor's.
+    pub resolution: UVec2,
+    /// Last window position in windowed mode, in physical pixels.
+    pub position: Option<IVec2>,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            mode: DisplayMode::Fullscreen,
+            monitor_id: None,
+            resolution: RESOLUTIONS[0],
+            position: None,
+        }
+    }
+}
+
+impl DisplayConfig {
+    pub fn toggle_mode(&mut self) {
+        self.mode = match self.mode {
+            DisplayMode::Fullscreen => DisplayMode::Windowed,
+            DisplayMode::Windowed => DisplayMode::Fullscreen,
+        };
+    }
+
+    /// Moves `steps` entries through the current monitor and then each of
+    /// the `plugged_in` ones, stopping at either end. An unplugged choice
+    /// steps from the end.
+    pub fn step_monitor(&mut self, steps: isize, plugged_in: &[(Entity, MonitorId)]) {
+        let count = plugged_in.len();
+        let current = self
+            .monitor_id
+            .as_ref()
+            .map_or(0, |id| id.find(plugged_in).map_or(count, |index| index + 1));
+        self.monitor_id = match current.saturating_add_signed(steps).min(count) {
+            0 => None,
+            index => Some(plugged_in[index - 1].1.clone()),
+        };
+    }
+
+    /// Moves `steps` entries through `RESOLUTIONS`, stopping at either end.
+    /// A size the window was dragged to steps to the nearest entry in that
+    /// direction.
+    pub fn step_resolution(&mut self, steps: isize) {
+        let index = match RESOLUTIONS
+            .iter()
+            .position(|resolution| *resolution == self.resolution)
+        {
+            Some(current) => current.saturating_add_signed(steps),
+            None => {
+                let area = self.resolution.element_product();
+                let larger =
+                    RESOLUTIONS.partition_point(|resolution| resolution.element_product() <= area);
+                if steps > 0 {
+                    larger.saturating_add_signed(steps - 1)
+                } else {
+                    larger.saturating_add_signed(steps)
+                }
+            }
+        };
+        self.resolution = RESOLUTIONS[index.min(RESOLUTIONS.len() - 1)];
+    }
+}
+
+fn load_display_config(mut commands: Commands, settings: Res<Settings>) {
+    commands.insert_resource(settings.display.clone());
+}
+
+fn store_display_config(config: Res<DisplayConfig>, mut settings: ResMut<Settings>) {
+    if settings.display != *config {
+        settings.display = config.clone();
+    }
+}
+
+fn apply_display_config(
+    config: Res<DisplayConfig>,
+    monitors: Query<(Entity, &Monitor)>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+) {
+    let plugged_in = plugged_in(monitors.iter());
+    let chosen = config
+        .monitor_id
+        .as_ref()
+        .and_then(|id| id.find(&plugged_in))
+        .map(|index| plugged_in[index].0);
+    let selection = chosen.map_or(MonitorSelection::Current, MonitorSelection::Entity);
+
+    match config.mode {
+        DisplayMode::Fullscreen => {
+            window.mode = WindowMode::BorderlessFullscreen(selection);
+            window.resizable = false;
+        }
+        DisplayMode::Windowed => {
+            window.mode = WindowMode::Windowed;
+            window.resizable = true;
+            // Settings saved before degenerate sizes were skipped may still
+            // hold one.
+            let resolution = if config.resolution.cmplt(MIN_WINDOW_SIZE).any() {
+                RESOLUTIONS[0]
+            } else {
+                config.resolution
+            };
+            window
+                .resolution
+                .set(resolution.x as f32, resolution.y as f32);
+
+            // A saved position on a monitor that's since been unplugged would
+            // open the window out of sight, and one on another monitor than
+            // the chosen one would ignore the choice.
+            let on_screen = |position: &IVec2| {
+                monitors
+                    .iter()
+                    .filter(|(entity, _)| chosen.is_none_or(|chosen| chosen == *entity))
+                    .any(|(_, monitor)| {
+                        IRect::from_corners(
+                            monitor.physical_position,
+                            monitor.physical_position + monitor.physical_size().as_ivec2(),
+                        )
+                        .contains(*position)
+                    })
+            };
+            window.position = match (config.position.filter(on_screen), chosen) {
+                (Some(position), _) => WindowPosition::At(position),
+                (None, Some(_)) => WindowPosition::Centered(selection),
+                (None, None) => WindowPosition::Automatic,
+            };
+        }
+    }
+}
+
+fn track_window_placement(
+    mut config: ResMut<DisplayConfig>,
+    mut moved: EventReader<WindowMoved>,
+    mut resized: EventReader<WindowResized>,
+    mut pending: Local<Option<(Timer, Option<IVec2>, Option<UVec2>)>>,
+    time: Res<Time>,
+) {
+    let windowed = config.mode == DisplayMode::Windowed;
+
+    for event in moved.read() {
+        if windowed {
+            let (timer, position, _) = pending.get_or_insert_default();
+            *timer = Timer::new(PLACEMENT_SETTLE, TimerMode::Once);
+            *position = Some(event.position);
+        }
+    }
+    for event in resized.read() {
+        let new_size = Vec2::new(event.width, event.height).round().as_uvec2();
+        if windowed && !new_size.cmplt(MIN_WINDOW_SIZE).any() {
+            let (timer, _, size) = pending.get_or_insert_default();
+            *timer = Timer::new(PLACEMENT_SETTLE, TimerMode::Once);
+            *size = Some(new_size);
+        }
+    }
+
+    let Some((timer, position, size)) = pending.as_mut() else {
+        return;
+    };
+    if !timer.tick(time.delta()).finished() {
+        return;
+    }
+
+    if let Some(position) = position.take() {
+        config.position = Some(position);
+    }
+    if let Some(size) = size.take() {
+        config.resolution = size;
+    }
+    *pending = None;
+}
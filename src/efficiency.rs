@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use bevy_third_person_camera::ThirdPersonCameraTarget;
+
+use crate::countdown::{RunStarted, RunTimer};
+use crate::levels::{Levels, SelectedLevel};
+use crate::objective::{DETONATOR_POSITION, Objective};
+use crate::stats::Stats;
+use crate::{GameState, WinGame};
+
+/// Measures how far the player rolls during a run and compares it with a
+/// greedy estimate of the shortest route through the objective.
+pub struct EfficiencyPlugin;
+
+impl Plugin for EfficiencyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunPath>()
+            .add_systems(
+                Update,
+                (
+                    reset_run_path.run_if(on_event::<RunStarted>),
+                    track_run_path.run_if(in_state(GameState::Game)),
+                )
+                    .chain(),
+            )
+            .add_systems(OnEnter(GameState::Win), show_efficiency);
+    }
+}
+
+#[derive(Component)]
+pub struct EfficiencyText;
+
+#[derive(Resource, Default)]
+pub struct RunPath {
+    pub distance: f32,
+    pub optimal: f32,
+    last: Option<Vec3>,
+}
+
+impl RunPath {
+    /// Percentage of the estimated optimal route length to the distance
+    /// actually travelled, capped at 100.
+    pub fn efficiency(&self) -> Option<f32> {
+        (self.distance > 0.0 && self.optimal > 0.0)
+            .then(|| (self.optimal / self.distance).min(1.0) * 100.0)
+    }
+}
+
+fn reset_run_path(mut path: ResMut<RunPath>) {
+    *path = RunPath::default();
+}
+
+fn track_run_path(
+    mut path: ResMut<RunPath>,
+    run_timer: Res<RunTimer>,
+    objective: Res<Objective>,
+    player: Query<&GlobalTransform, With<ThirdPersonCameraTarget>>,
+    atoms: Query<&GlobalTransform, With<WinGame>>,
+) {
+    if !run_timer.running {
+        return;
+    }
+
+    let Ok(player) = player.single() else {
+        return;
+    };
+    let position = player.translation().with_y(0.0);
+
+    match path.last {
+        Some(last) => path.distance += last.distance(position),
+        None => {
+            let atoms: Vec<Vec3> = atoms
+                .iter()
+                .map(|atom| atom.translation().with_y(0.0))
+                .collect();
+            path.optimal = estimate_route(position, atoms, objective.required as usize);
+        }
+    }
+    path.last = Some(position);
+}
+
+/// Nearest-neighbour route from `start` through `count` atoms and on to the
+/// detonator.
+fn estimate_route(start: Vec3, mut atoms: Vec<Vec3>, count: usize) -> f32 {
+    let mut position = start;
+    let mut length = 0.0;
+
+    for _ in 0..count {
+        let Some((index, _)) = atoms.iter().enumerate().min_by(|(_, a), (_, b)| {
+            a.distance_squared(position)
+                .total_cmp(&b.distance_squared(position))
+        }) else {
+            break;
+        };
+        let next = atoms.swap_remove(index);
+        length += position.distance(next);
+        position = next;
+    }
+
+    length + position.distance(DETONATOR_POSITION.with_y(0.0))
+}
+
+fn show_efficiency(
+    path: Res<RunPath>,
+    levels: Res<Levels>,
+    selected: Res<SelectedLevel>,
+    mut stats: ResMut<Stats>,
+    mut text_query: Query<&mut Text, With<EfficiencyText>>,
+) {
+    let Some(efficiency) = path.efficiency() else {
+        return;
+    };
+
+    let level = &selected.get(&levels).name;
+    let best = stats
+        .best_efficiency
+        .get(level)
+        .copied()
+        .unwrap_or(0.0)
+        .max(efficiency);
+    if stats.best_efficiency.get(level) != Some(&best) {
+        stats.best_efficiency.insert(level.clone(), best);
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.0 = format!("Route efficiency: {efficiency:.0}% (best {best:.0}%)");
+    }
+}
@@ -0,0 +1,192 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::GameState;
+use crate::settings::Settings;
+
+/// Fades to black and back around state changes. A requested state is held
+/// back until the screen is fully dark, so menus and cameras swap out of
+/// sight. Pausing and unpausing stay instant.
+pub struct FadePlugin;
+
+impl Plugin for FadePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FadeConfig>()
+            .init_resource::<Transition>()
+            .add_systems(Startup, setup_fade)
+            .add_systems(PostUpdate, follow_active_camera)
+            // After everything that might have asked for a new state.
+            .add_systems(
+                Last,
+                (hold_state_change, drive_transition, fade_visuals).chain(),
+            );
+    }
+}
+
+#[derive(Resource)]
+pub struct FadeConfig {
+    /// Time taken by each half of the fade.
+    pub duration: Duration,
+    /// Used instead with `Settings::reduce_motion`, so the screen doesn't
+    /// pulse dark on every change.
+    pub reduced_duration: Duration,
+}
+
+impl Default for FadeConfig {
+    fn default() -> Self {
+        Self {
+            duration: Duration::from_millis(250),
+            reduced_duration: Duration::from_millis(80),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FadeDirection {
+    /// Darkening towards a state change.
+    Out,
+    /// Lightening after one, or at rest once `alpha` is back to zero.
+    In,
+}
+
+#[derive(Resource)]
+pub struct Transition {
+    /// Darkness of the overlay, from 0 to 1.
+    pub alpha: f32,
+    pub direction: FadeDirection,
+    /// The state to switch to once the screen is dark.
+    target: Option<GameState>,
+}
+
+impl Default for Transition {
+    fn default() -> Self {
+        Self {
+            alpha: 0.0,
+            direction: FadeDirection::In,
+            target: None,
+        }
+    }
+}
+
+#[derive(Component)]
+struct FadeOverlay;
+
+/// Run condition for while no fade is under way, for input that shouldn't
+/// queue up another state change mid-fade.
+pub fn fade_idle(transition: Res<Transition>) -> bool {
+    transition.target.is_none() && transition.alpha == 0.0
+}
+
+fn setup_fade(mut commands: Commands) {
+    commands.spawn((
+        FadeOverlay,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.0)),
+        // Over every other UI, and keeps mid-fade clicks off the menus.
+        GlobalZIndex(100),
+        FocusPolicy::Block,
+        Visibility::Hidden,
+    ));
+}
+
+/// Whether the change can skip the fade.
+fn instant(from: &GameState, to: &GameState) -> bool {
+    matches!(
+        (from, to),
+        (GameState::Game, GameState::Pause) | (GameState::Pause, GameState::Game)
+    )
+}
+
+fn hold_state_change(
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut transition: ResMut<Transition>,
+) {
+    let NextState::Pending(target) = next_state.as_ref() else {
+        return;
+    };
+    if transition.target.is_none() && instant(state.get(), target) {
+        return;
+    }
+
+    // A later request replaces one still waiting for the dark.
+    transition.target = Some(target.clone());
+    transition.direction = FadeDirection::Out;
+    next_state.reset();
+}
+
+fn drive_transition(
+    config: Res<FadeConfig>,
+    settings: Res<Settings>,
+    time: Res<Time<Real>>,
+    mut transition: ResMut<Transition>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let duration = if settings.reduce_motion {
+        config.reduced_duration
+    } else {
+        config.duration
+    };
+    let step = time.delta_secs() / duration.as_secs_f32().max(f32::EPSILON);
+    match transition.direction {
+        FadeDirection::Out => {
+            transition.alpha = (transition.alpha + step).min(1.0);
+            if transition.alpha >= 1.0 {
+                if let Some(target) = transition.target.take() {
+                    next_state.set(target);
+                }
+                transition.direction = FadeDirection::In;
+            }
+        }
+        FadeDirection::In => {
+            if transition.alpha > 0.0 {
+                transition.alpha = (transition.alpha - step).max(0.0);
+            }
+        }
+    }
+}
+
+fn fade_visuals(
+    transition: Res<Transition>,
+    overlay: Single<(&mut BackgroundColor, &mut Visibility), With<FadeOverlay>>,
+) {
+    if !transition.is_changed() {
+        return;
+    }
+
+    let (mut color, mut visibility) = overlay.into_inner();
+    color.0 = Color::BLACK.with_alpha(transition.alpha);
+    visibility.set_if_neq(if transition.alpha > 0.0 {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    });
+}
+
+/// Cameras switch on and off with the state, so the overlay moves to the
+/// topmost active one.
+fn follow_active_camera(
+    mut commands: Commands,
+    overlay: Single<(Entity, Option<&UiTargetCamera>), With<FadeOverlay>>,
+    cameras: Query<(Entity, &Camera)>,
+) {
+    let Some((camera, _)) = cameras
+        .iter()
+        .filter(|(_, camera)| camera.is_active)
+        .max_by_key(|(_, camera)| camera.order)
+    else {
+        return;
+    };
+
+    let (entity, target) = *overlay;
+    if target.is_none_or(|target| target.entity() != camera) {
+        commands.entity(entity).insert(UiTargetCamera(camera));
+    }
+}
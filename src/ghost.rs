@@ -0,0 +1,137 @@
+use bevy::prelude::*;
+use bevy_third_person_camera::ThirdPersonCameraTarget;
+
+use crate::settings::Settings;
+use crate::{GameState, WinGame};
+
+/// Hard-mode modifier: atoms stay invisible until the player comes within
+/// the reveal radius, then fade in. Toggled from the settings screen, so it
+/// can also be turned off mid-run from the pause menu.
+pub struct GhostAtomsPlugin;
+
+impl Plugin for GhostAtomsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GhostConfig>().add_systems(
+            Update,
+            (
+                (prepare_ghost_materials, fade_ghost_atoms)
+                    .chain()
+                    .run_if(in_state(GameState::Game).and(ghost_atoms_enabled)),
+                reveal_ghost_atoms
+                    .run_if(not(ghost_atoms_enabled).and(any_with_component::<GhostMaterials>)),
+            ),
+        );
+    }
+}
+
+#[derive(Resource)]
+pub struct GhostConfig {
+    /// Distance from the player at which an atom is fully visible.
+    pub reveal_radius: f32,
+    /// Extra distance over which an atom fades in before it is fully visible.
+    pub fade_distance: f32,
+}
+
+impl Default for GhostConfig {
+    fn default() -> Self {
+        Self {
+            reveal_radius: 20.0,
+            fade_distance: 10.0,
+        }
+    }
+}
+
+impl GhostConfig {
+    /// Whether an atom this far from the player is still at least partly
+    /// hidden, so anything pointing it out should stay hidden too.
+    pub fn hides(&self, settings: &Settings, distance: f32) -> bool {
+        settings.ghost_atoms && distance > self.reveal_radius
+    }
+}
+
+/// The atom's own copies of its scene materials, so each atom can fade
+/// independently of the shared glTF materials.
+#[derive(Component)]
+struct GhostMaterials(Vec<Handle<StandardMaterial>>);
+
+pub fn ghost_atoms_enabled(settings: Res<Settings>) -> bool {
+    settings.ghost_atoms
+}
+
+fn prepare_ghost_materials(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    atoms: Query<Entity, (With<WinGame>, Without<GhostMaterials>)>,
+    children: Query<&Children>,
+    mut mesh_materials: Query<&mut MeshMaterial3d<StandardMaterial>>,
+) {
+    for atom in atoms.iter() {
+        let mut handles = Vec::new();
+
+        for descendant in children.iter_descendants(atom) {
+            let Ok(mut mesh_material) = mesh_materials.get_mut(descendant) else {
+                continue;
+            };
+            let Some(mut material) = materials.get(&mesh_material.0).cloned() else {
+                continue;
+            };
+
+            material.alpha_mode = AlphaMode::Blend;
+            material.base_color.set_alpha(0.0);
+            let handle = materials.add(material);
+            mesh_material.0 = handle.clone();
+            handles.push(handle);
+        }
+
+        // The scene may not have been instantiated yet; try again next frame.
+        if !handles.is_empty() {
+            commands.entity(atom).insert(GhostMaterials(handles));
+        }
+    }
+}
+
+fn fade_ghost_atoms(
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<GhostConfig>,
+    player: Query<&GlobalTransform, With<ThirdPersonCameraTarget>>,
+    atoms: Query<(&GlobalTransform, &GhostMaterials)>,
+) {
+    let Ok(player) = player.single() else {
+        return;
+    };
+
+    for (transform, ghost) in atoms.iter() {
+        let distance = transform.translation().distance(player.translation());
+        let alpha = ((config.reveal_radius + config.fade_distance - distance)
+            / config.fade_distance.max(f32::EPSILON))
+        .clamp(0.0, 1.0);
+
+        for handle in &ghost.0 {
+            let Some(material) = materials.get(handle) else {
+                continue;
+            };
+            if (material.base_color.alpha() - alpha).abs() < 0.01 {
+                continue;
+            }
+            if let Some(material) = materials.get_mut(handle) {
+                material.base_color.set_alpha(alpha);
+            }
+        }
+    }
+}
+
+/// Turned off part way through a run: every atom shows in full again.
+fn reveal_ghost_atoms(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    atoms: Query<(Entity, &GhostMaterials)>,
+) {
+    for (atom, ghost) in atoms.iter() {
+        for handle in &ghost.0 {
+            if let Some(material) = materials.get_mut(handle) {
+                material.base_color.set_alpha(1.0);
+            }
+        }
+        commands.entity(atom).remove::<GhostMaterials>();
+    }
+}
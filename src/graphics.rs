@@ -0,0 +1,129 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use bevy::window::{PresentMode, PrimaryWindow};
+use serde::{Deserialize, Serialize};
+
+use crate::settings::Settings;
+
+/// Frame rate caps offered, with `None` for uncapped.
+pub const FPS_LIMITS: [Option<u32>; 5] = [None, Some(30), Some(60), Some(120), Some(144)];
+
+/// Rendering switches for slower machines, saved with the rest of the
+/// settings.
+pub struct GraphicsPlugin;
+
+impl Plugin for GraphicsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreStartup, load_graphics_config)
+            .add_systems(
+                Update,
+                (
+                    apply_shadows,
+                    apply_present_mode.run_if(resource_changed::<GraphicsConfig>),
+                    store_graphics_config.run_if(resource_changed::<GraphicsConfig>),
+                ),
+            );
+
+        // Browsers pace frames themselves, and a wasm thread can't sleep.
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_systems(Last, limit_frame_rate);
+    }
+}
+
+#[derive(Resource, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GraphicsConfig {
+    /// Shadow maps are the biggest cost on integrated GPUs.
+    pub shadows: bool,
+    /// Wait for the display's refresh before showing each frame.
+    pub vsync: bool,
+    /// Most frames drawn per second, on top of vsync. One of `FPS_LIMITS`.
+    pub fps_limit: Option<u32>,
+}
+
+impl Default for GraphicsConfig {
+    fn default() -> Self {
+        Self {
+            shadows: true,
+            vsync: true,
+            fps_limit: None,
+        }
+    }
+}
+
+impl GraphicsConfig {
+    /// Moves `steps` entries through `FPS_LIMITS`, stopping at either end.
+    pub fn step_fps_limit(&mut self, steps: isize) {
+        let current = FPS_LIMITS
+            .iter()
+            .position(|limit| *limit == self.fps_limit)
+            .unwrap_or(0);
+        let index = current
+            .saturating_add_signed(steps)
+            .min(FPS_LIMITS.len() - 1);
+        self.fps_limit = FPS_LIMITS[index];
+    }
+}
+
+/// A directional or point light that casts shadows while
+/// `GraphicsConfig::shadows` is on. Lights without it never do.
+#[derive(Component)]
+pub struct ShadowLight;
+
+fn load_graphics_config(mut commands: Commands, settings: Res<Settings>) {
+    commands.insert_resource(settings.graphics.clone());
+}
+
+fn store_graphics_config(config: Res<GraphicsConfig>, mut settings: ResMut<Settings>) {
+    if settings.graphics != *config {
+        settings.graphics = config.clone();
+    }
+}
+
+/// Scenes bring their own lights, so new ones are set here as well as every
+/// one of them when the setting changes.
+fn apply_shadows(
+    config: Res<GraphicsConfig>,
+    mut directional: Query<(Ref<ShadowLight>, &mut DirectionalLight), Without<PointLight>>,
+    mut point: Query<(Ref<ShadowLight>, &mut PointLight), Without<DirectionalLight>>,
+) {
+    for (marker, mut light) in directional.iter_mut() {
+        if (config.is_changed() || marker.is_added()) && light.shadows_enabled != config.shadows {
+            light.shadows_enabled = config.shadows;
+        }
+    }
+    for (marker, mut light) in point.iter_mut() {
+        if (config.is_changed() || marker.is_added()) && light.shadows_enabled != config.shadows {
+            light.shadows_enabled = config.shadows;
+        }
+    }
+}
+
+fn apply_present_mode(
+    config: Res<GraphicsConfig>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+) {
+    let present_mode = if config.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+    if window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+}
+
+/// Sleeps out whatever is left of the frame's share of a second under the
+/// limit, measured from the end of the last one.
+#[cfg(not(target_arch = "wasm32"))]
+fn limit_frame_rate(config: Res<GraphicsConfig>, mut last_frame: Local<Option<Instant>>) {
+    if let (Some(limit), Some(last_frame)) = (config.fps_limit, *last_frame) {
+        let frame_time = Duration::from_secs_f64(1.0 / f64::from(limit.max(1)));
+        if let Some(remaining) = frame_time.checked_sub(last_frame.elapsed()) {
+            std::thread::sleep(remaining);
+        }
+    }
+    *last_frame = Some(Instant::now());
+}
@@ -0,0 +1,93 @@
+use bevy::asset::RenderAssetUsages;
+use bevy::color::ColorToPacked;
+use bevy::image::{ImageAddressMode, ImageSampler, ImageSamplerDescriptor};
+use bevy::math::Affine2;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+/// Pixels along each edge of the texture, which holds two squares a side.
+const TEXTURE_SIZE: u32 = 64;
+/// World units along each edge of a checker square.
+const SQUARE_SIZE: f32 = 4.0;
+
+/// A checkerboard over the ground, so the player can see how fast they're
+/// going. Like the sky it's built in code, so it can't fail to load; the
+/// plain colour is what shows in the moment before it reaches the GPU.
+pub struct GroundPlugin;
+
+impl Plugin for GroundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, build_ground_texture);
+    }
+}
+
+#[derive(Resource)]
+pub struct GroundAssets {
+    pub checker: Handle<Image>,
+}
+
+impl GroundAssets {
+    /// The ground material, tiled to keep squares the same size on any
+    /// ground.
+    pub fn material(&self, ground_size: f32) -> StandardMaterial {
+        StandardMaterial {
+            base_color_texture: Some(self.checker.clone()),
+            uv_transform: Affine2::from_scale(Vec2::splat(ground_size / (SQUARE_SIZE * 2.0))),
+            ..default()
+        }
+    }
+}
+
+fn build_ground_texture(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let light = Color::srgb(0.92, 0.92, 0.92);
+    let dark = Color::srgb(0.78, 0.78, 0.8);
+
+    // A full mip chain, or the squares shimmer into moiré in the distance.
+    // Each level is still an exact checkerboard down to one pixel a square,
+    // and the last is their average.
+    let levels = TEXTURE_SIZE.ilog2() + 1;
+    let mut data = Vec::new();
+    for level in 0..levels {
+        let size = TEXTURE_SIZE >> level;
+        let square = size / 2;
+        for row in 0..size {
+            for column in 0..size {
+                let color = if square == 0 {
+                    light.mix(&dark, 0.5)
+                } else if (row / square + column / square).is_multiple_of(2) {
+                    light
+                } else {
+                    dark
+                };
+                data.extend_from_slice(&color.to_srgba().to_u8_array());
+            }
+        }
+    }
+
+    // `Image::new` only expects the first level, so the rest go in after.
+    let first_level = (TEXTURE_SIZE * TEXTURE_SIZE * 4) as usize;
+    let mut image = Image::new(
+        Extent3d {
+            width: TEXTURE_SIZE,
+            height: TEXTURE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data[..first_level].to_vec(),
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.data = Some(data);
+    image.texture_descriptor.mip_level_count = levels;
+    image.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+        address_mode_u: ImageAddressMode::Repeat,
+        address_mode_v: ImageAddressMode::Repeat,
+        // The ground is mostly seen at a glancing angle.
+        anisotropy_clamp: 16,
+        ..ImageSamplerDescriptor::linear()
+    });
+
+    commands.insert_resource(GroundAssets {
+        checker: images.add(image),
+    });
+}
@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+use crate::hud::HealthFill;
+use crate::settings::{DifficultyConfig, Settings};
+use crate::{GameState, Player};
+
+/// Shrinks the HUD health bar as the player takes hazard hits. The hits
+/// themselves are handled in `hit_hazard`.
+pub struct HealthPlugin;
+
+impl Plugin for HealthPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, health_bar.run_if(in_state(GameState::Game)));
+    }
+}
+
+/// Hits left before the run is lost. Starts at the difficulty's
+/// `DifficultyTuning::health`.
+#[derive(Component)]
+pub struct Health(pub u32);
+
+fn health_bar(
+    settings: Res<Settings>,
+    difficulty: Res<DifficultyConfig>,
+    player: Query<Ref<Health>, With<Player>>,
+    mut fill: Query<&mut Node, With<HealthFill>>,
+) {
+    let Ok(health) = player.single() else {
+        return;
+    };
+    // Also true for each new run's player, so the bar refills. It starts
+    // full, so a HUD spawned after the player needs nothing.
+    if !health.is_changed() {
+        return;
+    }
+
+    let max = difficulty.tuning(settings.difficulty).health.max(1);
+    let fraction = health.0.min(max) as f32 / max as f32;
+    for mut node in fill.iter_mut() {
+        node.width = Val::Percent(fraction * 100.0);
+    }
+}
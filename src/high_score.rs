@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::GameState;
+use crate::countdown::RunTimer;
+use crate::levels::{Levels, SelectedLevel};
+use crate::storage;
+
+const HIGH_SCORE_PATH: &str = "high_score.ron";
+
+const NEW_BEST_COLOR: Color = Color::srgb(1.0, 0.8, 0.2);
+
+/// Fastest completion time for each level, shown on the main menu and
+/// celebrated on the win screen when beaten.
+pub struct HighScorePlugin;
+
+impl Plugin for HighScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(storage::load::<HighScore>(HIGH_SCORE_PATH))
+            .add_systems(OnEnter(GameState::Win), record_best_time)
+            .add_systems(Update, best_time_text.run_if(in_state(GameState::Menu)))
+            .add_systems(Last, save_high_score.run_if(resource_changed::<HighScore>));
+    }
+}
+
+#[derive(Resource, Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HighScore {
+    /// Best completion time in seconds, by level name.
+    pub best_times: HashMap<String, f32>,
+}
+
+/// The selected level's best time on the main menu.
+#[derive(Component)]
+pub struct BestTimeText;
+
+/// Shown on the win screen, highlighted when the run set a new best.
+#[derive(Component)]
+pub struct NewBestText;
+
+fn record_best_time(
+    run_timer: Res<RunTimer>,
+    levels: Res<Levels>,
+    selected: Res<SelectedLevel>,
+    mut high_score: ResMut<HighScore>,
+    mut text_query: Query<(&mut Text, &mut TextColor), With<NewBestText>>,
+) {
+    let time = run_timer.elapsed.as_secs_f32();
+    let level = &selected.get(&levels).name;
+    let previous = high_score.best_times.get(level).copied();
+    let new_best = previous.is_none_or(|best| time < best);
+    if new_best {
+        high_score.best_times.insert(level.clone(), time);
+    }
+
+    for (mut text, mut color) in text_query.iter_mut() {
+        match previous {
+            Some(best) if !new_best => {
+                text.0 = format!("Best: {best:.2}s");
+                color.0 = Color::srgb(0.9, 0.9, 0.9);
+            }
+            _ => {
+                text.0 = "New best!".to_string();
+                color.0 = NEW_BEST_COLOR;
+            }
+        }
+    }
+}
+
+fn best_time_text(
+    high_score: Res<HighScore>,
+    levels: Res<Levels>,
+    selected: Res<SelectedLevel>,
+    mut text_query: Query<&mut Text, With<BestTimeText>>,
+) {
+    if !high_score.is_changed() && !selected.is_changed() {
+        return;
+    }
+
+    let label = high_score
+        .best_times
+        .get(&selected.get(&levels).name)
+        .map(|best| format!("Best: {best:.2}s"))
+        .unwrap_or_default();
+    for mut text in text_query.iter_mut() {
+        text.0.clone_from(&label);
+    }
+}
+
+fn save_high_score(high_score: Res<HighScore>) {
+    storage::save(HIGH_SCORE_PATH, &*high_score);
+}
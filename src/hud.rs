@@ -0,0 +1,141 @@
+use bevy::prelude::*;
+use bevy_third_person_camera::ThirdPersonCamera;
+
+use crate::{FontAssets, GameState, Menu};
+
+/// In-game overlay. It renders through the game camera because the menu
+/// camera is inactive while playing.
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            setup_hud.run_if(in_state(GameState::Game).and(not(any_with_component::<Hud>))),
+        );
+    }
+}
+
+#[derive(Component)]
+pub struct Hud;
+
+#[derive(Component)]
+pub struct ObjectiveText;
+
+/// Atoms collected out of every atom in the level.
+#[derive(Component)]
+pub struct AtomCountText;
+
+#[derive(Component)]
+pub struct CountdownText;
+
+/// The layout seed, so a run can be shared and played again.
+#[derive(Component)]
+pub struct SeedText;
+
+#[derive(Component)]
+pub struct ComboText;
+
+/// The filled part of the health bar, as wide as the health left.
+#[derive(Component)]
+pub struct HealthFill;
+
+/// Shown while the player holds a combo shield.
+#[derive(Component)]
+pub struct ShieldIcon;
+
+fn setup_hud(
+    mut commands: Commands,
+    camera: Single<Entity, With<ThirdPersonCamera>>,
+    font_assets: Res<FontAssets>,
+) {
+    commands.spawn((
+        Hud,
+        Menu {
+            show_state: GameState::Game,
+        },
+        UiTargetCamera(*camera),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        children![
+            (
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(20.0),
+                    left: Val::Px(20.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                },
+                children![
+                    (
+                        Node {
+                            width: Val::Px(200.0),
+                            height: Val::Px(16.0),
+                            border: UiRect::all(Val::Px(2.0)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+                        BorderColor(Color::srgb(0.9, 0.9, 0.9)),
+                        children![(
+                            HealthFill,
+                            Node {
+                                width: Val::Percent(100.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.85, 0.15, 0.15)),
+                        )],
+                    ),
+                    hud_text(ObjectiveText, &font_assets),
+                    hud_text(AtomCountText, &font_assets),
+                    hud_text(ComboText, &font_assets),
+                    (
+                        ShieldIcon,
+                        Node {
+                            width: Val::Px(24.0),
+                            height: Val::Px(28.0),
+                            border: UiRect::all(Val::Px(3.0)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.2, 0.6, 0.9)),
+                        BorderColor(Color::srgb(0.9, 0.9, 0.9)),
+                        BorderRadius::bottom(Val::Percent(50.0)),
+                        Visibility::Hidden,
+                    ),
+                    hud_text(SeedText, &font_assets),
+                ],
+            ),
+            (
+                CountdownText,
+                Text::default(),
+                TextFont {
+                    font: font_assets.u_atom.clone(),
+                    font_size: 160.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextShadow::default(),
+            ),
+        ],
+    ));
+}
+
+fn hud_text(marker: impl Component, assets: &FontAssets) -> impl Bundle {
+    (
+        marker,
+        Text::default(),
+        TextFont {
+            font: assets.u_atom.clone(),
+            font_size: 28.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.9, 0.9, 0.9)),
+        TextShadow::default(),
+    )
+}
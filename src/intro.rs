@@ -0,0 +1,215 @@
+use bevy::prelude::*;
+
+use crate::graphics::ShadowLight;
+use crate::levels::{LevelConfig, Levels, SelectedLevel};
+use crate::settings::{DifficultyConfig, Settings};
+use crate::stats::Stats;
+use crate::{AtomAssets, FontAssets, GameState, RespawnConfig};
+
+const INTRO_SECS: f32 = 8.0;
+const TITLE_FADE: (f32, f32) = (2.5, 4.5);
+
+/// Camera keyframes as (time in seconds, offset from the atom field centre).
+const CAMERA_PATH: [(f32, Vec3); 4] = [
+    (0.0, Vec3::new(-90.0, 60.0, -90.0)),
+    (3.0, Vec3::new(0.0, 40.0, -80.0)),
+    (6.0, Vec3::new(75.0, 28.0, -20.0)),
+    (INTRO_SECS, Vec3::new(70.0, 22.0, 30.0)),
+];
+
+/// A short fly-over of the atom field, played once before the main menu on
+/// the first run. Any key or click skips it.
+pub struct IntroPlugin;
+
+impl Plugin for IntroPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Intro), start_intro)
+            .add_systems(
+                Update,
+                (play_intro, skip_intro)
+                    .chain()
+                    .run_if(in_state(GameState::Intro).and(resource_exists::<Intro>)),
+            )
+            .add_systems(OnExit(GameState::Intro), end_intro);
+    }
+}
+
+#[derive(Resource)]
+struct Intro {
+    elapsed: f32,
+    center: Vec3,
+}
+
+#[derive(Component)]
+struct IntroEntity;
+
+#[derive(Component)]
+struct IntroCamera;
+
+#[derive(Component)]
+struct IntroTitle;
+
+fn start_intro(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    stats: Res<Stats>,
+    atom_assets: Res<AtomAssets>,
+    font_assets: Res<FontAssets>,
+    levels: Res<Levels>,
+    selected: Res<SelectedLevel>,
+    level_config: Res<LevelConfig>,
+    respawn: Res<RespawnConfig>,
+    settings: Res<Settings>,
+    difficulty: Res<DifficultyConfig>,
+) {
+    if stats.has_played {
+        next_state.set(GameState::Menu);
+        return;
+    }
+
+    let level = selected.get(&levels);
+    let positions = level.atoms.positions(
+        &level_config,
+        difficulty.tuning(settings.difficulty),
+        respawn.spawn_point,
+    );
+    let center = positions.iter().sum::<Vec3>() / positions.len().max(1) as f32;
+
+    commands.spawn((
+        Mesh3d(
+            meshes.add(
+                Plane3d::default()
+                    .mesh()
+                    .size(level.ground_size, level.ground_size),
+            ),
+        ),
+        MeshMaterial3d(materials.add(Color::WHITE)),
+        IntroEntity,
+    ));
+
+    for position in positions {
+        commands.spawn((
+            SceneRoot(atom_assets.u_atom.clone()),
+            Transform::from_translation(position).looking_to(Vec3::Z, Vec3::Y),
+            IntroEntity,
+        ));
+    }
+
+    commands.spawn((
+        DirectionalLight {
+            illuminance: 4000.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        ShadowLight,
+        Transform::default().looking_to(Vec3::new(0.3, -1.0, 0.2), Vec3::Z),
+        IntroEntity,
+    ));
+
+    // Drawn after the menu camera, which is already active when the intro is
+    // replayed from the settings screen.
+    let camera = commands
+        .spawn((
+            Camera {
+                order: 1,
+                ..default()
+            },
+            Camera3d::default(),
+            Transform::from_translation(center + CAMERA_PATH[0].1).looking_at(center, Vec3::Y),
+            IntroCamera,
+            IntroEntity,
+        ))
+        .id();
+
+    commands.spawn((
+        UiTargetCamera(camera),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        IntroEntity,
+        children![(
+            IntroTitle,
+            Text::new("Fissile Material"),
+            TextFont {
+                font: font_assets.u_atom.clone(),
+                font_size: 100.0,
+                ..default()
+            },
+            TextColor(Color::srgba(0.9, 0.9, 0.9, 0.0)),
+            TextShadow {
+                color: Color::NONE,
+                ..default()
+            },
+        )],
+    ));
+
+    commands.insert_resource(Intro {
+        elapsed: 0.0,
+        center,
+    });
+}
+
+fn play_intro(
+    mut intro: ResMut<Intro>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut camera: Single<&mut Transform, With<IntroCamera>>,
+    mut title: Single<(&mut TextColor, &mut TextShadow), With<IntroTitle>>,
+    time: Res<Time>,
+) {
+    intro.elapsed += time.delta_secs();
+
+    let offset = camera_offset(intro.elapsed);
+    **camera = Transform::from_translation(intro.center + offset).looking_at(intro.center, Vec3::Y);
+
+    let (start, end) = TITLE_FADE;
+    let alpha = ((intro.elapsed - start) / (end - start)).clamp(0.0, 1.0);
+    let (color, shadow) = &mut *title;
+    color.0.set_alpha(alpha);
+    shadow.color = Color::BLACK.with_alpha(alpha * 0.75);
+
+    if intro.elapsed >= INTRO_SECS {
+        next_state.set(GameState::Menu);
+    }
+}
+
+fn camera_offset(elapsed: f32) -> Vec3 {
+    let Some(index) = CAMERA_PATH.windows(2).position(|pair| elapsed < pair[1].0) else {
+        return CAMERA_PATH[CAMERA_PATH.len() - 1].1;
+    };
+
+    let (from_time, from) = CAMERA_PATH[index];
+    let (to_time, to) = CAMERA_PATH[index + 1];
+    let t = ((elapsed - from_time) / (to_time - from_time)).clamp(0.0, 1.0);
+    from.lerp(to, t * t * (3.0 - 2.0 * t))
+}
+
+fn skip_intro(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard.get_just_pressed().next().is_some() || mouse.get_just_pressed().next().is_some() {
+        next_state.set(GameState::Menu);
+    }
+}
+
+fn end_intro(
+    mut commands: Commands,
+    mut stats: ResMut<Stats>,
+    query: Query<Entity, With<IntroEntity>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+    commands.remove_resource::<Intro>();
+
+    if !stats.has_played {
+        stats.has_played = true;
+    }
+}
@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::settings::Settings;
+use crate::theme::ColorTheme;
+use crate::{GameState, Player};
+
+/// A short window after a hit in which further hits are ignored, so brushing
+/// a hazard costs one hit rather than one a frame. The ball blinks while it
+/// lasts.
+pub struct InvinciblePlugin;
+
+impl Plugin for InvinciblePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InvincibleConfig>()
+            .add_systems(Update, tick_invincible.run_if(in_state(GameState::Game)));
+    }
+}
+
+#[derive(Resource)]
+pub struct InvincibleConfig {
+    pub duration: Duration,
+    /// Blinks per second.
+    pub blink_rate: f32,
+    /// Colour the ball flashes to.
+    pub blink_color: Color,
+}
+
+impl Default for InvincibleConfig {
+    fn default() -> Self {
+        Self {
+            duration: Duration::from_millis(1500),
+            blink_rate: 8.0,
+            blink_color: Color::WHITE,
+        }
+    }
+}
+
+/// Hits are ignored until the timer runs out.
+#[derive(Component)]
+pub struct Invincible(pub Timer);
+
+impl Invincible {
+    pub fn new(config: &InvincibleConfig) -> Self {
+        Self(Timer::new(config.duration, TimerMode::Once))
+    }
+}
+
+/// With reduce motion on, the ball holds the blink colour rather than
+/// flashing.
+fn tick_invincible(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<InvincibleConfig>,
+    settings: Res<Settings>,
+    theme: Res<ColorTheme>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut player: Query<(Entity, &mut Invincible, &MeshMaterial3d<StandardMaterial>), With<Player>>,
+) {
+    for (entity, mut invincible, material) in player.iter_mut() {
+        let finished = invincible.0.tick(time.delta()).finished();
+        if finished {
+            commands.entity(entity).remove::<Invincible>();
+        }
+
+        let lit = !finished
+            && (settings.reduce_motion
+                || ((invincible.0.elapsed_secs() * config.blink_rate * 2.0) as u32)
+                    .is_multiple_of(2));
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.base_color = if lit {
+                config.blink_color
+            } else {
+                theme.player
+            };
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+use bevy_third_person_camera::ThirdPersonCamera;
+
+use crate::controls::{Action, KeyBindings};
+use crate::coop::Players;
+use crate::settings::Settings;
+use crate::{CameraConfig, GameState};
+
+/// Camera turn speed in radians per second at a sensitivity of 1.
+const LOOK_SPEED: f32 = 1.8;
+
+/// Closest the camera gets to looking straight up or down, in radians.
+const PITCH_MARGIN: f32 = 0.1;
+
+/// Lets the whole game be played without a mouse. With
+/// `Settings::keyboard_only` on:
+///
+/// - the arrow keys turn the camera, scaled by the sensitivity setting,
+/// - movement, jump, sprint, dash, rewind and pause stay on their bindings
+///   (W/A/S/D, Space, Left Shift, Left Ctrl, R and Escape unless changed),
+/// - every menu, including the controls and pause screens, is driven with
+///   the arrow keys and Enter,
+/// - C still frees the cursor, though nothing needs it.
+///
+/// An arrow key bound to an action is left to that action. In two-player
+/// mode the arrow keys belong to player two, so mouse look is the only look.
+pub struct KeyboardPlayPlugin;
+
+impl Plugin for KeyboardPlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            keyboard_look.run_if(
+                in_state(GameState::Game)
+                    .and(keyboard_only)
+                    .and(resource_equals(Players(1))),
+            ),
+        );
+    }
+}
+
+fn keyboard_only(settings: Res<Settings>) -> bool {
+    settings.keyboard_only
+}
+
+/// Turns the camera the way the mouse would. The camera plugin places it
+/// around the player from its rotation afterwards.
+fn keyboard_look(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    camera_config: Res<CameraConfig>,
+    time: Res<Time>,
+    mut camera: Single<&mut Transform, With<ThirdPersonCamera>>,
+) {
+    let held = |key: KeyCode| {
+        keyboard.pressed(key)
+            && !Action::ALL
+                .into_iter()
+                .any(|action| bindings.get(action) == key)
+    };
+    let axis = |negative: KeyCode, positive: KeyCode| match (held(negative), held(positive)) {
+        (true, false) => -1.0,
+        (false, true) => 1.0,
+        _ => 0.0,
+    };
+
+    let turn = Vec2::new(
+        axis(KeyCode::ArrowLeft, KeyCode::ArrowRight),
+        axis(KeyCode::ArrowDown, KeyCode::ArrowUp),
+    );
+    if turn == Vec2::ZERO {
+        return;
+    }
+    let turn = turn * LOOK_SPEED * camera_config.sensitivity * time.delta_secs();
+
+    let (yaw, pitch, _) = camera.rotation.to_euler(EulerRot::YXZ);
+    let pitch = (pitch + turn.y).clamp(-FRAC_PI_2 + PITCH_MARGIN, FRAC_PI_2 - PITCH_MARGIN);
+    camera.rotation = Quat::from_euler(EulerRot::YXZ, yaw - turn.x, pitch, 0.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::input::ButtonState;
+    use bevy::input::InputPlugin;
+    use bevy::input::keyboard::{Key, KeyboardInput, NativeKey};
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use bevy_third_person_camera::ThirdPersonCameraTarget;
+
+    use super::*;
+    use crate::capture::CaptureConfig;
+    use crate::chain::{ChainConfig, ChainReaction};
+    use crate::combo::Combo;
+    use crate::controls::Rebinding;
+    use crate::countdown::RunTimer;
+    use crate::menu_nav::MenuNavPlugin;
+    use crate::objective::{Collected, Detonator, Objective, ObjectivePhase};
+    use crate::screen_shake::ScreenShake;
+    use crate::shockwave::ShockwaveConfig;
+    use crate::stats::{Stats, UnsavedAtoms};
+    use crate::theme::{ColorPreset, ColorTheme};
+    use crate::{
+        AtomCollected, AtomValue, BlastConfig, CollisionWith, DeathCountText, GameOver, Menu,
+        PendingGameOver, SoundAssets, StartButton, WinGame, collision_response, end_game,
+        finish_game_over, key_leave_win, start_button_system,
+    };
+
+    fn key(app: &mut App, key_code: KeyCode, state: ButtonState) {
+        app.world_mut().send_event(KeyboardInput {
+            key_code,
+            logical_key: Key::Unidentified(NativeKey::Unidentified),
+            state,
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+    }
+
+    /// Taps `key_code` and gives the press, and any state change it asks for,
+    /// a few frames to go through.
+    fn tap(app: &mut App, key_code: KeyCode) {
+        key(app, key_code, ButtonState::Pressed);
+        app.update();
+        key(app, key_code, ButtonState::Released);
+        for _ in 0..3 {
+            app.update();
+        }
+    }
+
+    fn state(app: &App) -> GameState {
+        app.world().resource::<State<GameState>>().get().clone()
+    }
+
+    #[test]
+    fn plays_through_without_a_mouse() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            InputPlugin,
+            AssetPlugin::default(),
+        ))
+        .init_asset::<Mesh>()
+        .init_asset::<StandardMaterial>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            50,
+        )))
+        .insert_resource(Settings {
+            keyboard_only: true,
+            ..default()
+        })
+        .insert_resource(ColorTheme::new(ColorPreset::Default))
+        .init_resource::<KeyBindings>()
+        .init_resource::<CameraConfig>()
+        .init_resource::<Players>()
+        .init_resource::<Rebinding>()
+        // What collecting the last atom and setting off the detonator
+        // touch on the way to the win screen.
+        .insert_resource(SoundAssets {
+            u_atom: Handle::default(),
+            pickup: Handle::default(),
+            fanfare: Handle::default(),
+            hover: Handle::default(),
+            click: Handle::default(),
+        })
+        .insert_resource(Objective::new(1, 1.0))
+        .insert_resource(RunTimer {
+            running: true,
+            ..default()
+        })
+        .init_resource::<Collected>()
+        .init_resource::<UnsavedAtoms>()
+        .init_resource::<Stats>()
+        .init_resource::<Combo>()
+        .init_resource::<CaptureConfig>()
+        .init_resource::<ChainConfig>()
+        .init_resource::<ChainReaction>()
+        .init_resource::<ScreenShake>()
+        .init_resource::<BlastConfig>()
+        .init_resource::<ShockwaveConfig>()
+        .add_event::<CollisionWith>()
+        .add_event::<AtomCollected>()
+        .add_event::<GameOver>()
+        .insert_state(GameState::Menu)
+        .add_plugins((MenuNavPlugin, KeyboardPlayPlugin))
+        .add_systems(
+            Update,
+            (
+                start_button_system.run_if(in_state(GameState::Menu)),
+                key_leave_win.run_if(in_state(GameState::Win)),
+                end_game.run_if(on_event::<GameOver>),
+                finish_game_over
+                    .run_if(resource_exists::<PendingGameOver>.and(in_state(GameState::Game))),
+            ),
+        )
+        .add_systems(
+            FixedUpdate,
+            collision_response.run_if(on_event::<CollisionWith>),
+        );
+
+        // Without the UI plugins nothing computes visibility, so the button
+        // is marked visible by hand.
+        app.world_mut().spawn((
+            Menu {
+                show_state: GameState::Menu,
+            },
+            children![(Button, StartButton, InheritedVisibility::VISIBLE)],
+        ));
+        let camera = app
+            .world_mut()
+            .spawn((ThirdPersonCamera::default(), Transform::default()))
+            .id();
+        app.world_mut().spawn((
+            ThirdPersonCameraTarget,
+            Transform::default(),
+            GlobalTransform::default(),
+        ));
+        let placed = |x: f32| {
+            (
+                Transform::from_xyz(x, 0.0, 0.0),
+                GlobalTransform::from_xyz(x, 0.0, 0.0),
+            )
+        };
+        let atom = app
+            .world_mut()
+            .spawn((WinGame, AtomValue(1), placed(20.0)))
+            .id();
+        let detonator = app.world_mut().spawn((Detonator, placed(-20.0))).id();
+        app.world_mut().spawn((Text::default(), DeathCountText));
+        app.update();
+
+        // Down focuses Play, Enter presses it.
+        tap(&mut app, KeyCode::ArrowDown);
+        tap(&mut app, KeyCode::Enter);
+        assert_eq!(state(&app), GameState::Game);
+
+        // Right and up turn the camera right and tilt it up.
+        key(&mut app, KeyCode::ArrowRight, ButtonState::Pressed);
+        key(&mut app, KeyCode::ArrowUp, ButtonState::Pressed);
+        for _ in 0..5 {
+            app.update();
+        }
+        key(&mut app, KeyCode::ArrowRight, ButtonState::Released);
+        key(&mut app, KeyCode::ArrowUp, ButtonState::Released);
+        app.update();
+        let rotation = app.world().get::<Transform>(camera).unwrap().rotation;
+        let (yaw, pitch, _) = rotation.to_euler(EulerRot::YXZ);
+        assert!(yaw < 0.0, "yaw {yaw}");
+        assert!(pitch > 0.0, "pitch {pitch}");
+
+        // Touching the last atom collects it and arms the detonator. Contact
+        // comes from physics, so it's sent as the contact systems would.
+        app.world_mut().send_event(CollisionWith(atom));
+        app.update();
+        assert!(app.world().get_entity(atom).is_err());
+        assert_eq!(
+            app.world().resource::<Objective>().phase,
+            ObjectivePhase::Armed
+        );
+
+        // Touching the detonator wins, once the blast has flung the ball.
+        app.world_mut().send_event(CollisionWith(detonator));
+        let fling = Duration::from_secs_f32(BlastConfig::default().fling_secs);
+        for _ in 0..fling.div_duration_f32(Duration::from_millis(50)).ceil() as u32 + 2 {
+            app.update();
+        }
+        assert_eq!(state(&app), GameState::Win);
+
+        // Escape leaves it for the menu.
+        tap(&mut app, KeyCode::Escape);
+        assert_eq!(state(&app), GameState::Menu);
+    }
+}
@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+use bevy_third_person_camera::ThirdPersonCamera;
+
+use crate::ghost::GhostConfig;
+use crate::settings::Settings;
+use crate::{AtomValue, FontAssets, GameState, Player};
+
+const LABEL_HEIGHT: f32 = 6.0;
+const LABEL_SIZE: Vec2 = Vec2::new(80.0, 32.0);
+
+/// Optional point values floating above each atom. The labels are UI text
+/// placed over the atom's projected position, so they always face the camera.
+/// Ghost atoms only get theirs once revealed.
+pub struct AtomLabelsPlugin;
+
+impl Plugin for AtomLabelsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                spawn_atom_labels.run_if(in_state(GameState::Game)),
+                position_atom_labels,
+            )
+                .chain(),
+        );
+    }
+}
+
+#[derive(Component)]
+struct AtomLabel {
+    atom: Entity,
+}
+
+/// Marks an atom that already has a label.
+#[derive(Component)]
+struct Labelled;
+
+fn spawn_atom_labels(
+    mut commands: Commands,
+    camera: Single<Entity, With<ThirdPersonCamera>>,
+    font_assets: Res<FontAssets>,
+    atoms: Query<(Entity, &AtomValue), Without<Labelled>>,
+) {
+    for (atom, value) in atoms.iter() {
+        commands.entity(atom).insert(Labelled);
+        commands.spawn((
+            AtomLabel { atom },
+            UiTargetCamera(*camera),
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Px(LABEL_SIZE.x),
+                height: Val::Px(LABEL_SIZE.y),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            Visibility::Hidden,
+            children![(
+                Text::new(format!("+{}", value.0)),
+                TextFont {
+                    font: font_assets.u_atom.clone(),
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextShadow::default(),
+            )],
+        ));
+    }
+}
+
+fn position_atom_labels(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    ghost: Res<GhostConfig>,
+    state: Res<State<GameState>>,
+    camera: Query<(&Camera, &GlobalTransform), With<ThirdPersonCamera>>,
+    player: Query<&GlobalTransform, With<Player>>,
+    atoms: Query<&GlobalTransform>,
+    mut labels: Query<(Entity, &AtomLabel, &mut Node, &mut Visibility)>,
+) {
+    let camera = camera.single().ok();
+    let player = player.single().ok().map(GlobalTransform::translation);
+    let show = settings.atom_labels && *state.get() == GameState::Game;
+
+    for (entity, label, mut node, mut visibility) in labels.iter_mut() {
+        // Collected or torn-down atoms take their label with them.
+        let Ok(atom) = atoms.get(label.atom) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+
+        let revealed = player.map_or(!settings.ghost_atoms, |player| {
+            !ghost.hides(&settings, atom.translation().distance(player))
+        });
+        let viewport = camera
+            .filter(|_| show && revealed)
+            .and_then(|(camera, transform)| {
+                camera
+                    .world_to_viewport(transform, atom.translation() + Vec3::Y * LABEL_HEIGHT)
+                    .ok()
+            });
+
+        let Some(position) = viewport else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+
+        node.left = Val::Px(position.x - LABEL_SIZE.x / 2.0);
+        node.top = Val::Px(position.y - LABEL_SIZE.y / 2.0);
+        visibility.set_if_neq(Visibility::Inherited);
+    }
+}
@@ -0,0 +1,572 @@
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use serde::Deserialize;
+
+use crate::hud::SeedText;
+use crate::replay::{ReplayMenuAction, replay_button};
+use crate::settings::DifficultyTuning;
+use crate::stats::Stats;
+use crate::{FontAssets, GameState, Menu, NORMAL_BUTTON};
+
+/// Minimum distance between the centres of two scattered atoms, leaving a
+/// gap between their radius 4 colliders.
+const SCATTER_SPACING: f32 = 10.0;
+/// Scattered atoms keep at least this far from the player's spawn point.
+const SCATTER_SPAWN_CLEARANCE: f32 = 12.0;
+
+/// Loads every level definition from `assets/levels/` and lets the player
+/// pick one from the level select screen. Only the first level is open at
+/// first; winning a level unlocks the one after it. The layout seed is shown
+/// during and after each run and can be typed in on the level select screen,
+/// so a layout can be shared and raced on again.
+pub struct LevelsPlugin;
+
+impl Plugin for LevelsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Levels::load())
+            .init_resource::<SelectedLevel>()
+            .init_resource::<LevelConfig>()
+            .init_resource::<SeedEntry>()
+            .add_systems(PreStartup, restore_selected_level)
+            .add_systems(
+                Update,
+                (
+                    setup_level_select.run_if(in_state(GameState::Menu).and(run_once)),
+                    level_button_system
+                        .run_if(in_state(GameState::Menu).or(in_state(GameState::LevelSelect))),
+                    (level_labels, type_seed, seed_label)
+                        .chain()
+                        .run_if(in_state(GameState::LevelSelect)),
+                    seed_text.run_if(in_state(GameState::Game).or(in_state(GameState::Win))),
+                ),
+            )
+            .add_systems(OnEnter(GameState::Win), record_completion)
+            .add_systems(OnExit(GameState::LevelSelect), stop_seed_entry);
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct LevelDefinition {
+    pub name: String,
+    /// Explicit position in the level list. Levels without one sort after
+    /// those with one, by file name.
+    #[serde(default)]
+    pub index: Option<u32>,
+    #[serde(default = "default_ground_size")]
+    pub ground_size: f32,
+    pub atoms: AtomLayout,
+    #[serde(default)]
+    pub ramps: Vec<RampDefinition>,
+    /// Countdown length in whole seconds, in place of
+    /// `CountdownConfig::seconds`.
+    #[serde(default)]
+    pub countdown: Option<u32>,
+    /// Share of atom spots, from 0 to 1, taken by hazards instead. Picked by
+    /// `LevelConfig::seed` like the moving atoms. None unless asked for.
+    #[serde(default)]
+    pub hazard_fraction: f32,
+}
+
+fn default_ground_size() -> f32 {
+    1024.0
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub enum AtomLayout {
+    /// Atoms on a square grid, kept within `radius` of `center` (x, z).
+    Disc {
+        center: (f32, f32),
+        radius: f32,
+        spacing: f32,
+    },
+    /// Atoms at explicit (x, z) positions.
+    Points(Vec<(f32, f32)>),
+    /// `LevelConfig::atom_count` atoms, scaled by difficulty, scattered
+    /// within `radius` of `center` (x, z), laid out by `LevelConfig::seed`.
+    Scatter { center: (f32, f32), radius: f32 },
+}
+
+/// A straight incline rising from the ground. Tnua's walk basis handles
+/// slopes up to 45 degrees, but the ball floats well above the surface, so
+/// gentle ones (under about 20 degrees) climb most smoothly.
+#[derive(Clone, Debug, Deserialize)]
+pub struct RampDefinition {
+    /// Centre of the ramp's footprint (x, z).
+    pub center: (f32, f32),
+    /// Compass direction the ramp rises towards, in degrees. 0 rises
+    /// towards +z.
+    pub heading: f32,
+    /// Horizontal distance from the foot to the top.
+    pub length: f32,
+    pub width: f32,
+    /// Height of the top edge above the ground.
+    pub rise: f32,
+}
+
+/// Thickness of the slab each ramp is made from.
+pub const RAMP_THICKNESS: f32 = 1.0;
+
+impl RampDefinition {
+    /// Size of the tilted slab, along its own axes.
+    pub fn size(&self) -> Vec3 {
+        Vec3::new(
+            self.width,
+            RAMP_THICKNESS,
+            Vec2::new(self.length, self.rise).length(),
+        )
+    }
+
+    /// Places the slab so its top surface runs from the ground at the foot
+    /// up to `rise` at the top.
+    pub fn transform(&self) -> Transform {
+        let pitch = self.rise.atan2(self.length);
+        let rotation =
+            Quat::from_rotation_y(self.heading.to_radians()) * Quat::from_rotation_x(-pitch);
+        let top_center = Vec3::new(self.center.0, self.rise / 2.0, self.center.1);
+        Transform::from_translation(top_center - rotation * Vec3::Y * RAMP_THICKNESS / 2.0)
+            .with_rotation(rotation)
+    }
+}
+
+/// Parameters for procedurally laid out levels, and the walls around every
+/// level.
+#[derive(Resource)]
+pub struct LevelConfig {
+    pub atom_count: u32,
+    /// The same seed always produces the same layout.
+    pub seed: u64,
+    /// Half the width of the walled-in play area, measured from the origin.
+    /// Levels with smaller ground have their walls at its edge instead.
+    pub bound: f32,
+    pub wall_height: f32,
+    /// Share of atoms, from 0 to 1, that sway back and forth. Picked by
+    /// `seed`, so a seeded level always moves the same ones.
+    pub moving_fraction: f32,
+    pub moving_speed: f32,
+    pub moving_range: f32,
+}
+
+impl Default for LevelConfig {
+    fn default() -> Self {
+        Self {
+            atom_count: 20,
+            seed: rand::random(),
+            bound: 512.0,
+            wall_height: 30.0,
+            moving_fraction: 0.25,
+            moving_speed: 4.0,
+            moving_range: 6.0,
+        }
+    }
+}
+
+impl LevelConfig {
+    /// Where the walls stand for a level, as a distance from the origin.
+    pub fn wall_distance(&self, level: &LevelDefinition) -> f32 {
+        self.bound.min(level.ground_size / 2.0)
+    }
+}
+
+impl AtomLayout {
+    /// Atom centres, resting on the ground.
+    pub fn positions(
+        &self,
+        config: &LevelConfig,
+        tuning: &DifficultyTuning,
+        spawn_point: Vec3,
+    ) -> Vec<Vec3> {
+        match self {
+            AtomLayout::Disc {
+                center,
+                radius,
+                spacing,
+            } => {
+                let center = Vec3::new(center.0, 4.0, center.1);
+                let steps = (radius / spacing).ceil() as i32;
+                let mut positions = Vec::new();
+                for i in -steps..=steps {
+                    for j in -steps..=steps {
+                        let position =
+                            center + Vec3::new(*spacing * j as f32, 0.0, *spacing * i as f32);
+                        if position.distance(center) < *radius {
+                            positions.push(position);
+                        }
+                    }
+                }
+                positions
+            }
+            AtomLayout::Points(points) => {
+                points.iter().map(|(x, z)| Vec3::new(*x, 4.0, *z)).collect()
+            }
+            AtomLayout::Scatter { center, radius } => {
+                let center = Vec3::new(center.0, 4.0, center.1);
+                let spawn_point = spawn_point.with_y(4.0);
+                let atom_count = (config.atom_count as f32 * tuning.atom_factor).round() as u32;
+                let mut rng = StdRng::seed_from_u64(config.seed);
+                let mut positions: Vec<Vec3> = Vec::new();
+
+                // Rejection sampling; a crowded area just ends up with fewer
+                // atoms rather than looping forever.
+                for _ in 0..atom_count * 50 {
+                    if positions.len() as u32 >= atom_count {
+                        break;
+                    }
+
+                    let angle = rng.random_range(0.0..std::f32::consts::TAU);
+                    // sqrt keeps the density even across the disc.
+                    let distance = radius * rng.random::<f32>().sqrt();
+                    let position = center + Vec3::new(angle.cos(), 0.0, angle.sin()) * distance;
+
+                    if position.distance(spawn_point) >= SCATTER_SPAWN_CLEARANCE
+                        && positions
+                            .iter()
+                            .all(|other| other.distance(position) >= SCATTER_SPACING)
+                    {
+                        positions.push(position);
+                    }
+                }
+
+                positions
+            }
+        }
+    }
+}
+
+impl LevelDefinition {
+    /// The original hand-placed atom field, used when no level files load.
+    fn classic() -> Self {
+        Self {
+            name: "Classic".to_string(),
+            index: Some(0),
+            ground_size: default_ground_size(),
+            atoms: AtomLayout::Disc {
+                center: (60.0, 0.0),
+                radius: 30.0,
+                spacing: 9.0,
+            },
+            ramps: Vec::new(),
+            countdown: None,
+            hazard_fraction: 0.0,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct Levels(pub Vec<LevelDefinition>);
+
+impl Levels {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load() -> Self {
+        use bevy::asset::io::file::FileAssetReader;
+
+        let directory = FileAssetReader::get_base_path().join("assets/levels");
+        let mut files: Vec<_> = match std::fs::read_dir(&directory) {
+            Ok(entries) => entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "ron"))
+                .collect(),
+            Err(err) => {
+                warn!("Could not read {}: {err}", directory.display());
+                Vec::new()
+            }
+        };
+        files.sort();
+
+        let mut levels: Vec<LevelDefinition> = files
+            .iter()
+            .filter_map(|path| {
+                let contents = std::fs::read_to_string(path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|contents| {
+                        ron::from_str::<LevelDefinition>(&contents).map_err(|err| err.to_string())
+                    });
+                contents
+                    .inspect_err(|err| warn!("Skipping level {}: {err}", path.display()))
+                    .ok()
+            })
+            .collect();
+
+        // Stable sort keeps file name order among levels without an index.
+        levels.sort_by_key(|level| level.index.unwrap_or(u32::MAX));
+
+        if levels.is_empty() {
+            levels.push(LevelDefinition::classic());
+        }
+
+        Self(levels)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn load() -> Self {
+        Self(vec![LevelDefinition::classic()])
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct SelectedLevel(pub usize);
+
+impl SelectedLevel {
+    pub fn get<'a>(&self, levels: &'a Levels) -> &'a LevelDefinition {
+        &levels.0[self.0.min(levels.0.len() - 1)]
+    }
+}
+
+impl Levels {
+    /// The first level is always open, the rest once the one before has
+    /// been won.
+    pub fn unlocked(&self, index: usize, stats: &Stats) -> bool {
+        index == 0
+            || self
+                .0
+                .get(index - 1)
+                .is_some_and(|previous| stats.completed_levels.contains(&previous.name))
+    }
+}
+
+/// Picks the last played level again, falling back to the first if it has
+/// since been removed.
+fn restore_selected_level(
+    levels: Res<Levels>,
+    stats: Res<Stats>,
+    mut selected: ResMut<SelectedLevel>,
+) {
+    let Some(last_level) = &stats.last_level else {
+        return;
+    };
+    if let Some(index) = levels.0.iter().position(|level| &level.name == last_level) {
+        selected.0 = index;
+    }
+}
+
+fn record_completion(levels: Res<Levels>, selected: Res<SelectedLevel>, mut stats: ResMut<Stats>) {
+    let level = &selected.get(&levels).name;
+    if !stats.completed_levels.contains(level) {
+        stats.completed_levels.insert(level.clone());
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+pub enum LevelMenuAction {
+    Open,
+    Select(usize),
+    /// Starts typing in a seed, or finishes if already typing.
+    EnterSeed,
+    Back,
+}
+
+/// Digits typed so far while entering a seed on the level select screen.
+#[derive(Resource, Default)]
+struct SeedEntry(Option<String>);
+
+fn level_button_system(
+    interaction_query: Query<
+        (&Interaction, &LevelMenuAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    levels: Res<Levels>,
+    mut selected: ResMut<SelectedLevel>,
+    mut stats: ResMut<Stats>,
+    mut seed_entry: ResMut<SeedEntry>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, action) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match *action {
+            LevelMenuAction::Open => next_state.set(GameState::LevelSelect),
+            LevelMenuAction::Select(index) => {
+                if !levels.unlocked(index, &stats) {
+                    continue;
+                }
+                selected.0 = index;
+                stats.last_level = Some(levels.0[index].name.clone());
+                next_state.set(GameState::Game);
+            }
+            LevelMenuAction::EnterSeed => {
+                seed_entry.0 = match seed_entry.0 {
+                    Some(_) => None,
+                    None => Some(String::new()),
+                };
+            }
+            LevelMenuAction::Back => next_state.set(GameState::Menu),
+        }
+    }
+}
+
+/// Dims the names of locked levels.
+fn level_labels(
+    levels: Res<Levels>,
+    stats: Res<Stats>,
+    buttons: Query<(&LevelMenuAction, &Children)>,
+    mut labels: Query<&mut TextColor>,
+) {
+    if !stats.is_changed() {
+        return;
+    }
+
+    for (action, children) in buttons.iter() {
+        let LevelMenuAction::Select(index) = *action else {
+            continue;
+        };
+        let color = if levels.unlocked(index, &stats) {
+            Color::srgb(0.9, 0.9, 0.9)
+        } else {
+            Color::srgb(0.4, 0.4, 0.4)
+        };
+        let mut labels = labels.iter_many_mut(children);
+        while let Some(mut label) = labels.fetch_next() {
+            label.0 = color;
+        }
+    }
+}
+
+/// Types digits into the seed while it's being entered, using each one as
+/// soon as it makes a valid seed.
+fn type_seed(
+    mut keys: EventReader<KeyboardInput>,
+    mut seed_entry: ResMut<SeedEntry>,
+    mut level_config: ResMut<LevelConfig>,
+) {
+    let Some(digits) = seed_entry.0.as_mut() else {
+        keys.clear();
+        return;
+    };
+
+    for key in keys.read() {
+        if key.state != ButtonState::Pressed {
+            continue;
+        }
+        match &key.logical_key {
+            Key::Character(typed) if typed.chars().all(|c| c.is_ascii_digit()) => {
+                let longer = format!("{digits}{typed}");
+                // Anything past u64::MAX can't be a seed.
+                if longer.parse::<u64>().is_ok() {
+                    *digits = longer;
+                }
+            }
+            Key::Backspace => {
+                digits.pop();
+            }
+            _ => continue,
+        }
+        if let Ok(seed) = digits.parse() {
+            level_config.seed = seed;
+        }
+    }
+}
+
+fn stop_seed_entry(mut seed_entry: ResMut<SeedEntry>) {
+    seed_entry.0 = None;
+}
+
+fn seed_label(
+    seed_entry: Res<SeedEntry>,
+    level_config: Res<LevelConfig>,
+    buttons: Query<(&LevelMenuAction, &Children)>,
+    mut labels: Query<&mut Text>,
+) {
+    if !seed_entry.is_changed() && !level_config.is_changed() {
+        return;
+    }
+
+    let label = match &seed_entry.0 {
+        Some(digits) => format!("Seed: {digits}_"),
+        None => format!("Seed: {}", level_config.seed),
+    };
+    for (action, children) in buttons.iter() {
+        if let (LevelMenuAction::EnterSeed, Ok(mut text)) = (action, labels.get_mut(children[0])) {
+            text.0 = label.clone();
+        }
+    }
+}
+
+fn seed_text(level_config: Res<LevelConfig>, mut text_query: Query<&mut Text, With<SeedText>>) {
+    for mut text in text_query.iter_mut() {
+        text.0 = format!("Seed: {}", level_config.seed);
+    }
+}
+
+fn setup_level_select(mut commands: Commands, font_assets: Res<FontAssets>, levels: Res<Levels>) {
+    commands
+        .spawn((
+            Menu {
+                show_state: GameState::LevelSelect,
+            },
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            Visibility::Hidden,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Select Level"),
+                TextFont {
+                    font: font_assets.u_atom.clone(),
+                    font_size: 80.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextShadow::default(),
+            ));
+
+            for (index, level) in levels.0.iter().enumerate() {
+                parent.spawn(level_button(
+                    &level.name,
+                    LevelMenuAction::Select(index),
+                    &font_assets,
+                ));
+            }
+
+            parent.spawn(level_button(
+                "Seed",
+                LevelMenuAction::EnterSeed,
+                &font_assets,
+            ));
+
+            parent.spawn(replay_button(
+                "Load Ghost",
+                ReplayMenuAction::Import,
+                &font_assets,
+            ));
+
+            parent.spawn(level_button("Back", LevelMenuAction::Back, &font_assets));
+        });
+}
+
+fn level_button(label: &str, action: LevelMenuAction, assets: &FontAssets) -> impl Bundle {
+    (
+        Button,
+        action,
+        Node {
+            width: Val::Px(300.0),
+            height: Val::Px(80.0),
+            border: UiRect::all(Val::Px(5.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BorderColor(Color::BLACK),
+        BorderRadius::MAX,
+        BackgroundColor(NORMAL_BUTTON),
+        children![(
+            Text::new(label),
+            TextFont {
+                font: assets.u_atom.clone(),
+                font_size: 38.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+            TextShadow::default(),
+        )],
+    )
+}
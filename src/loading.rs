@@ -0,0 +1,193 @@
+use bevy::asset::UntypedAssetLoadFailedEvent;
+use bevy::prelude::*;
+use iyes_progress::ProgressTracker;
+
+use crate::{GameState, Menu, NORMAL_BUTTON};
+
+/// A bar across the middle of the screen that fills as the asset
+/// collections load. Nothing else is up yet, so it brings its own camera.
+///
+/// If anything fails to load, the game stops at an error screen naming the
+/// missing files, with a button to try loading again.
+pub struct LoadingScreenPlugin;
+
+impl Plugin for LoadingScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LoadFailures>()
+            .add_systems(OnEnter(GameState::Loading), setup_loading_screen)
+            .add_systems(
+                Update,
+                (
+                    // Failures can still be arriving as the error screen
+                    // comes up.
+                    record_load_failures
+                        .run_if(in_state(GameState::Loading).or(in_state(GameState::LoadError))),
+                    loading_progress.run_if(in_state(GameState::Loading)),
+                    (load_error_text, retry_button_system).run_if(in_state(GameState::LoadError)),
+                )
+                    .chain(),
+            )
+            .add_systems(OnExit(GameState::Loading), teardown_loading_screen)
+            .add_systems(OnEnter(GameState::LoadError), setup_load_error_screen)
+            .add_systems(OnExit(GameState::LoadError), teardown_loading_screen);
+    }
+}
+
+/// Paths of the assets that failed during the current attempt.
+#[derive(Resource, Default)]
+struct LoadFailures(Vec<String>);
+
+/// Everything spawned for the loading or error screen.
+#[derive(Component)]
+struct LoadingScreen;
+
+#[derive(Component)]
+struct LoadErrorText;
+
+#[derive(Component)]
+struct RetryButton;
+
+#[derive(Component)]
+struct LoadingFill;
+
+fn setup_loading_screen(mut commands: Commands) {
+    commands.spawn((LoadingScreen, Camera2d));
+    commands.spawn((
+        LoadingScreen,
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        children![(
+            Node {
+                width: Val::Percent(40.0),
+                height: Val::Px(24.0),
+                border: UiRect::all(Val::Px(3.0)),
+                ..default()
+            },
+            BorderColor(Color::srgb(0.9, 0.9, 0.9)),
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+            children![(
+                LoadingFill,
+                Node {
+                    width: Val::Percent(0.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.9, 0.9, 0.9)),
+            )],
+        )],
+    ));
+}
+
+fn loading_progress(
+    tracker: Res<ProgressTracker<GameState>>,
+    mut fill: Single<&mut Node, With<LoadingFill>>,
+) {
+    let progress = tracker.get_global_progress();
+    let fraction = if progress.total == 0 {
+        0.0
+    } else {
+        progress.done as f32 / progress.total as f32
+    };
+    fill.width = Val::Percent(fraction * 100.0);
+}
+
+fn record_load_failures(
+    mut events: EventReader<UntypedAssetLoadFailedEvent>,
+    mut failures: ResMut<LoadFailures>,
+) {
+    for event in events.read() {
+        error!("Failed to load {}: {}", event.path, event.error);
+        let path = event.path.to_string();
+        if !failures.0.contains(&path) {
+            failures.0.push(path);
+        }
+    }
+}
+
+/// Uses Bevy's built-in font, since the game's own may be what's missing.
+fn setup_load_error_screen(mut commands: Commands) {
+    commands.spawn((LoadingScreen, Camera2d));
+    commands.spawn((
+        LoadingScreen,
+        Menu {
+            show_state: GameState::LoadError,
+        },
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(20.0),
+            ..default()
+        },
+        children![
+            (
+                LoadErrorText,
+                Text::default(),
+                TextFont {
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextLayout::new_with_justify(JustifyText::Center),
+            ),
+            (
+                Button,
+                RetryButton,
+                Node {
+                    width: Val::Px(300.0),
+                    height: Val::Px(80.0),
+                    border: UiRect::all(Val::Px(5.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BorderColor(Color::BLACK),
+                BorderRadius::MAX,
+                BackgroundColor(NORMAL_BUTTON),
+                children![(
+                    Text::new("Retry"),
+                    TextFont {
+                        font_size: 38.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                )],
+            ),
+        ],
+    ));
+}
+
+fn load_error_text(failures: Res<LoadFailures>, mut text: Single<&mut Text, With<LoadErrorText>>) {
+    let message = if failures.0.is_empty() {
+        "Some game files could not be loaded.".to_string()
+    } else {
+        format!("Could not load:\n{}", failures.0.join("\n"))
+    };
+    text.set_if_neq(Text(message));
+}
+
+fn retry_button_system(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<RetryButton>)>,
+    mut failures: ResMut<LoadFailures>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            failures.0.clear();
+            next_state.set(GameState::Loading);
+        }
+    }
+}
+
+fn teardown_loading_screen(mut commands: Commands, screen: Query<Entity, With<LoadingScreen>>) {
+    for entity in screen.iter() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -1,11 +1,16 @@
 use avian3d::prelude::*;
 use bevy::asset::AssetMetaCheck;
-use bevy::log::tracing::Instrument;
+use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
+use bevy::ecs::entity::Entities;
+use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
+use bevy::pbr::NotShadowCaster;
 use bevy::render::view::RenderLayers;
 use bevy::{
     color::palettes::css,
     prelude::*,
-    window::{WindowMode, WindowResolution},
+    window::{
+        CursorGrabMode, PresentMode, PrimaryWindow, WindowFocused, WindowMode, WindowResolution,
+    },
 };
 use bevy_asset_loader::asset_collection::AssetCollection;
 use bevy_asset_loader::prelude::*;
@@ -13,10 +18,129 @@ use bevy_seedling::prelude::*;
 use bevy_seedling::sample::Sample;
 use bevy_third_person_camera::*;
 
-use bevy_tnua::{TnuaProximitySensor, prelude::*};
+use bevy_tnua::builtins::TnuaBuiltinDash;
+use bevy_tnua::control_helpers::TnuaSimpleAirActionsCounter;
+use bevy_tnua::{TnuaProximitySensor, TnuaToggle, prelude::*};
 use bevy_tnua_avian3d::*;
+use iyes_progress::ProgressPlugin;
 use rand::prelude::*;
-use rand_distr::{Distribution, Normal, NormalError};
+use rand_distr::Normal;
+use serde::{Deserialize, Serialize};
+
+mod attraction;
+mod audio;
+mod benchmark;
+mod bonus_atom;
+mod boundary;
+mod burst;
+mod camera_collision;
+mod capture;
+mod celebration;
+mod chain;
+mod chain_camera;
+mod chaser;
+mod combo;
+mod controls;
+mod coop;
+mod countdown;
+mod day_night;
+mod debug_overlay;
+mod display;
+mod efficiency;
+mod fade;
+mod ghost;
+mod graphics;
+mod ground;
+mod health;
+mod high_score;
+mod hud;
+mod intro;
+mod invincible;
+mod keyboard_play;
+mod labels;
+mod levels;
+mod loading;
+mod menu_background;
+mod menu_nav;
+mod minimap;
+mod moving_atom;
+mod objective;
+mod physics_debug;
+mod player_light;
+mod quit_confirm;
+mod replay;
+mod rewind;
+mod rumble;
+mod scorch;
+mod screen_shake;
+mod sensitivity_preview;
+mod settings;
+mod settings_menu;
+mod shockwave;
+mod sky;
+mod stability;
+mod stats;
+mod storage;
+mod theme;
+mod tutorial;
+mod wall_jump;
+
+use attraction::AttractionPlugin;
+use audio::AudioPoolsPlugin;
+use benchmark::{BenchmarkMenuAction, BenchmarkPlugin};
+use bonus_atom::{BonusAtomConfig, BonusAtomPlugin, TimedAtom, spawn_bonus_atoms};
+use boundary::BoundaryPlugin;
+use burst::BurstPlugin;
+use camera_collision::CameraCollisionPlugin;
+use capture::{Capture, CaptureConfig, CapturePlugin};
+use celebration::CelebrationPlugin;
+use chain::{ChainConfig, ChainFuse, ChainPlugin, ChainReaction};
+use chain_camera::ChainCameraPlugin;
+use chaser::{ChaserAssets, ChaserConfig, ChaserPlugin, spawn_chasers};
+use combo::{Combo, ComboPlugin, ComboShield};
+use controls::{ControlsMenuAction, ControlsPlugin, KeyBindings};
+use coop::{AnyPlayer, CoopCamera, CoopConfig, CoopPlugin, Players, SecondPlayer};
+use countdown::{Countdown, CountdownPlugin, RunTimeText, RunTimer};
+use day_night::{DayNightPlugin, Sun};
+use debug_overlay::DebugOverlayPlugin;
+use display::DisplayPlugin;
+use efficiency::{EfficiencyPlugin, EfficiencyText};
+use fade::{FadePlugin, fade_idle};
+use ghost::GhostAtomsPlugin;
+use graphics::{GraphicsPlugin, ShadowLight};
+use ground::{GroundAssets, GroundPlugin};
+use health::{Health, HealthPlugin};
+use high_score::{BestTimeText, HighScorePlugin, NewBestText};
+use hud::{HudPlugin, SeedText};
+use intro::IntroPlugin;
+use invincible::{Invincible, InvincibleConfig, InvinciblePlugin};
+use keyboard_play::KeyboardPlayPlugin;
+use labels::AtomLabelsPlugin;
+use levels::{LevelConfig, LevelMenuAction, Levels, LevelsPlugin, SelectedLevel};
+use loading::LoadingScreenPlugin;
+use menu_background::MenuBackgroundPlugin;
+use menu_nav::MenuNavPlugin;
+use minimap::MinimapPlugin;
+use moving_atom::{MovingAtom, MovingAtomPlugin};
+use objective::{Collected, Detonator, Objective, ObjectivePlugin};
+use physics_debug::PhysicsDebugTogglePlugin;
+use player_light::{PlayerLightConfig, PlayerLightPlugin, player_light};
+use quit_confirm::{QuitConfirm, QuitConfirmPlugin};
+use replay::{ReplayMenuAction, ReplayPlugin, replay_button};
+use rewind::RewindPlugin;
+use rumble::RumblePlugin;
+use scorch::ScorchPlugin;
+use screen_shake::{ScreenShake, ScreenShakePlugin};
+use sensitivity_preview::SensitivityPreviewPlugin;
+use settings::{AtomPickup, DifficultyConfig, InputMode, Settings, SettingsPlugin};
+use settings_menu::{SettingsMenuAction, SettingsMenuPlugin};
+use shockwave::{ShockwaveConfig, ShockwavePlugin, spawn_shockwave};
+use sky::{EnvAssets, SkyConfig, SkyPlugin};
+use stability::StabilityPlugin;
+use stats::{LifetimeAtomsText, Stats, StatsMenuAction, StatsPlugin, UnsavedAtoms};
+use theme::{ColorTheme, ThemePlugin};
+use tutorial::TutorialPlugin;
+use wall_jump::{WallContact, WallJumpPlugin};
 
 fn main() {
     App::new()
@@ -35,21 +159,98 @@ fn main() {
                         position: WindowPosition::Automatic,
                         mode: WindowMode::BorderlessFullscreen(MonitorSelection::Current),
                         resolution: WindowResolution::default(),
+                        // Until the saved graphics settings are applied.
+                        present_mode: PresentMode::AutoVsync,
                         visible: true,
                         ..default()
                     }),
                     ..default()
                 }),
+            FrameTimeDiagnosticsPlugin::default(),
             PhysicsPlugins::default(),
+            PhysicsDebugPlugin::default(),
             TnuaControllerPlugin::new(FixedUpdate),
             TnuaAvian3dPlugin::new(FixedUpdate),
             SeedlingPlugin::default(),
             ThirdPersonCameraPlugin,
+            ProgressPlugin::<GameState>::new()
+                .with_state_transition(GameState::Loading, GameState::Intro),
+        ))
+        .add_plugins((
+            SettingsPlugin,
+            ScorchPlugin,
+            HudPlugin,
+            ObjectivePlugin,
+            CountdownPlugin,
+            AttractionPlugin,
+            GhostAtomsPlugin,
+            LevelsPlugin,
+            StatsPlugin,
+            EfficiencyPlugin,
+            ControlsPlugin,
+            SettingsMenuPlugin,
+            IntroPlugin,
+            DisplayPlugin,
+            ComboPlugin,
+        ))
+        .add_plugins((
+            ReplayPlugin,
+            AtomLabelsPlugin,
+            RewindPlugin,
+            StabilityPlugin,
+            AudioPoolsPlugin,
+            CelebrationPlugin,
+            PlayerLightPlugin,
+            CameraCollisionPlugin,
+            CapturePlugin,
+            BenchmarkPlugin,
+            ChaserPlugin,
+            SensitivityPreviewPlugin,
+            MenuNavPlugin,
+            QuitConfirmPlugin,
+        ))
+        .add_plugins((
+            ChainPlugin,
+            ChainCameraPlugin,
+            KeyboardPlayPlugin,
+            DebugOverlayPlugin,
+            PhysicsDebugTogglePlugin,
+            HighScorePlugin,
+            ThemePlugin,
+            LoadingScreenPlugin,
+            ScreenShakePlugin,
+            BurstPlugin,
+            FadePlugin,
+            GraphicsPlugin,
+            DayNightPlugin,
+            MovingAtomPlugin,
+            BonusAtomPlugin,
+        ))
+        .add_plugins((
+            MinimapPlugin,
+            RumblePlugin,
+            MenuBackgroundPlugin,
+            TutorialPlugin,
+            InvinciblePlugin,
+            HealthPlugin,
+            SkyPlugin,
+            GroundPlugin,
+            ShockwavePlugin,
+            CoopPlugin,
+            WallJumpPlugin,
+            BoundaryPlugin,
         ))
         .init_state::<GameState>()
+        .init_resource::<MovementConfig>()
+        .init_resource::<PhysicsFeelConfig>()
+        .init_resource::<LoseCause>()
+        .init_resource::<BlastConfig>()
+        .init_resource::<RespawnConfig>()
+        .init_resource::<NewLayout>()
         .add_loading_state(
+            // `ProgressPlugin` moves on to the intro once everything is in.
             LoadingState::new(GameState::Loading)
-                .continue_to_state(GameState::Menu)
+                .on_failure_continue_to_state(GameState::LoadError)
                 .load_collection::<AtomAssets>()
                 .load_collection::<FontAssets>()
                 .load_collection::<SoundAssets>(),
@@ -59,38 +260,96 @@ fn main() {
             (
                 (game_camera, show_menu).run_if(state_changed::<GameState>),
                 (setup_menu).run_if(in_state(GameState::Menu).and(run_once)),
-                (start_button_system, exit_button_system, text_timer).run_if(
-                    in_state(GameState::Menu)
-                        .or(in_state(GameState::Win))
-                        .or(in_state(GameState::Pause)),
-                ),
-                key_pause.run_if(in_state(GameState::Game)),
+                (button_visuals, button_sounds),
+                credits_button_system
+                    .run_if(in_state(GameState::Menu).or(in_state(GameState::Credits))),
+                (
+                    start_button_system,
+                    new_layout_button_system,
+                    exit_button_system,
+                    text_timer,
+                )
+                    .run_if(
+                        in_state(GameState::Menu)
+                            .or(in_state(GameState::Win))
+                            .or(in_state(GameState::Lose))
+                            .or(in_state(GameState::Pause)),
+                    ),
+                (
+                    key_pause,
+                    pause_on_focus_loss,
+                    sprint_input,
+                    catch_presses,
+                    respawn_fallen_player,
+                )
+                    .run_if(in_state(GameState::Game)),
+                cursor_lock_input,
+                scroll_zoom.run_if(in_state(GameState::Game)),
+                apply_camera_config.run_if(resource_changed::<CameraConfig>),
+                apply_physics_feel.run_if(resource_changed::<PhysicsFeelConfig>),
                 key_unpause.run_if(in_state(GameState::Pause)),
-                (setup_camera_and_lights, setup_level)
-                    .run_if(in_state(GameState::Game).and(run_once)),
+                key_leave_win.run_if(in_state(GameState::Win).and(fade_idle)),
+                setup_camera_and_lights.run_if(in_state(GameState::Game).and(run_once)),
+                setup_level
+                    .run_if(in_state(GameState::Game).and(not(any_with_component::<LevelEntity>))),
                 setup_player.run_if(
                     in_state(GameState::Game)
                         .and(not(any_with_component::<ThirdPersonCameraTarget>)),
                 ),
-                collision_response.run_if(on_event::<CollisionWith>),
                 end_game.run_if(on_event::<GameOver>),
-                (detect_atom).run_if(in_state(GameState::Game)),
+                finish_game_over
+                    .run_if(resource_exists::<PendingGameOver>.and(in_state(GameState::Game))),
             ),
         )
+        .add_systems(OnExit(GameState::Win), teardown_level)
+        .add_systems(OnExit(GameState::Lose), teardown_level)
+        .add_systems(OnEnter(GameState::Lose), lose_summary_text)
+        .add_systems(OnEnter(GameState::Menu), teardown_level)
+        // Settings can be opened from the pause menu, and the run has to stay
+        // frozen there too.
+        .add_systems(OnEnter(GameState::Pause), pause_physics)
+        .add_systems(OnExit(GameState::Pause), resume_physics)
+        .add_systems(OnEnter(GameState::Settings), pause_physics)
+        .add_systems(OnExit(GameState::Settings), resume_physics)
+        .add_systems(
+            OnTransition {
+                exited: GameState::Game,
+                entered: GameState::Pause,
+            },
+            take_pause_snapshot,
+        )
+        .add_systems(
+            OnTransition {
+                exited: GameState::Pause,
+                entered: GameState::Game,
+            },
+            restore_pause_snapshot,
+        )
         .add_systems(
             FixedUpdate,
-            apply_controls
-                .in_set(TnuaUserControlsSystemSet)
-                .run_if(in_state(GameState::Game)),
+            (
+                apply_controls
+                    .in_set(TnuaUserControlsSystemSet)
+                    .run_if(in_state(GameState::Game)),
+                // Collected on the fixed step, so pickups and the combo
+                // window run the same at any framerate.
+                (
+                    (detect_atom, detect_atom_overlap).run_if(in_state(GameState::Game)),
+                    (collision_response, hit_hazard).run_if(on_event::<CollisionWith>),
+                )
+                    .chain()
+                    .in_set(AtomCollectionSet)
+                    .after(TnuaPipelineStages::Sensors),
+            ),
         )
         .add_event::<CollisionWith>()
+        .add_event::<AtomCollected>()
         .add_event::<GameOver>()
         .run();
 }
 
+/// What buttons are spawned with, before `ColorTheme` repaints them.
 const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
-const HOVERED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
-const PRESSED_BUTTON: Color = Color::srgb(0.35, 0.75, 0.35);
 
 #[derive(AssetCollection, Resource)]
 pub struct AtomAssets {
@@ -108,36 +367,283 @@ pub struct FontAssets {
 pub struct SoundAssets {
     #[asset(path = "HugeExplosion2.wav")]
     u_atom: Handle<Sample>,
+    #[asset(path = "Pickup.wav")]
+    pickup: Handle<Sample>,
+    #[asset(path = "Fanfare.wav")]
+    fanfare: Handle<Sample>,
+    #[asset(path = "Hover.wav")]
+    hover: Handle<Sample>,
+    #[asset(path = "Click.wav")]
+    click: Handle<Sample>,
+}
+
+#[derive(Resource)]
+struct MovementConfig {
+    move_speed: f32,
+    /// Multiplier applied to `move_speed` while sprinting. Jumps are unaffected.
+    sprint_factor: f32,
+    float_height: f32,
+    jump_height: f32,
+    /// How long a jump press is held on to while it can't be used yet, such
+    /// as just before landing.
+    jump_buffer: Duration,
+    /// How long after rolling off a ledge a jump still counts as from the
+    /// ground.
+    coyote_time: Duration,
+    /// How far a dash carries the player.
+    dash_distance: f32,
+    /// Speed during a dash. Sprinting doesn't add to it, and the player
+    /// slows back to their walking or sprinting speed afterwards.
+    dash_speed: f32,
+    /// Time after a dash before the next one is allowed.
+    dash_cooldown: Duration,
+}
+
+impl Default for MovementConfig {
+    fn default() -> Self {
+        Self {
+            move_speed: 20.0,
+            sprint_factor: 1.75,
+            float_height: 4.0,
+            jump_height: 2.5,
+            jump_buffer: Duration::from_millis(150),
+            coyote_time: Duration::from_millis(120),
+            dash_distance: 12.0,
+            dash_speed: 60.0,
+            dash_cooldown: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Surface response of the ball itself. Tnua keeps it floating above the
+/// ground, so these mostly show against walls, ramps and solid atoms.
+#[derive(Resource)]
+struct PhysicsFeelConfig {
+    /// From 0 (ice) upwards. Avian's default of 0.5 lets the ball glance
+    /// off walls without sticking to them.
+    friction: f32,
+    /// From 0 (no bounce) to 1 (no energy lost). Kept at 0 so landings and
+    /// wall hits don't throw off the controller.
+    restitution: f32,
+}
+
+impl Default for PhysicsFeelConfig {
+    fn default() -> Self {
+        Self {
+            friction: 0.5,
+            restitution: 0.0,
+        }
+    }
+}
+
+#[derive(Resource)]
+struct BlastConfig {
+    /// Speed the player is launched at when standing right on the blast.
+    launch_speed: f32,
+    /// Distance at which the blast falls off to its minimum strength.
+    radius: f32,
+    /// How long the flung ball is followed before the win screen shows.
+    fling_secs: f32,
+}
+
+impl Default for BlastConfig {
+    fn default() -> Self {
+        Self {
+            launch_speed: 45.0,
+            radius: 40.0,
+            fling_secs: 1.5,
+        }
+    }
+}
+
+#[derive(Resource)]
+struct RespawnConfig {
+    /// The player is respawned once its height drops below this.
+    fall_threshold: f32,
+    spawn_point: Vec3,
+}
+
+impl Default for RespawnConfig {
+    fn default() -> Self {
+        Self {
+            fall_threshold: -20.0,
+            spawn_point: Vec3::new(0.0, 4.0, 0.0),
+        }
+    }
+}
+
+#[derive(Resource, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+struct CameraConfig {
+    /// Mouse look speed, applied to both axes.
+    sensitivity: f32,
+    /// Vertical field of view of the game camera, in degrees.
+    fov: f32,
+    /// Distance from the player to the camera, set with the scroll wheel.
+    zoom: f32,
+}
+
+impl CameraConfig {
+    const SENSITIVITY_RANGE: (f32, f32) = (0.1, 5.0);
+    const FOV_RANGE: (f32, f32) = (45.0, 110.0);
+    const ZOOM_RANGE: (f32, f32) = (1.5, 6.0);
+
+    fn adjust_sensitivity(&mut self, delta: f32) {
+        let (min, max) = Self::SENSITIVITY_RANGE;
+        // Round to the step so repeated presses don't accumulate float error.
+        self.sensitivity = ((self.sensitivity + delta) * 10.0).round() / 10.0;
+        self.sensitivity = self.sensitivity.clamp(min, max);
+    }
+
+    fn adjust_fov(&mut self, delta: f32) {
+        let (min, max) = Self::FOV_RANGE;
+        self.fov = (self.fov + delta).round().clamp(min, max);
+    }
+
+    /// Scales the zoom, so each step feels the same near and far.
+    fn adjust_zoom(&mut self, factor: f32) {
+        let (min, max) = Self::ZOOM_RANGE;
+        self.zoom = (self.zoom * factor).clamp(min, max);
+    }
+
+    /// The camera plugin keeps its zoom private and only clamps it, so
+    /// pinning both bounds to our own zoom is how it's set.
+    fn zoom(&self) -> Zoom {
+        Zoom::new(self.zoom, self.zoom)
+    }
+
+    fn projection(&self) -> Projection {
+        Projection::Perspective(PerspectiveProjection {
+            fov: self.fov.to_radians(),
+            ..default()
+        })
+    }
+}
+
+impl Default for CameraConfig {
+    fn default() -> Self {
+        Self {
+            sensitivity: 1.0,
+            // Bevy's own default.
+            fov: 45.0,
+            // Halfway between the camera plugin's default bounds.
+            zoom: 2.25,
+        }
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Hash, Default, States)]
 pub enum GameState {
     #[default]
     Loading,
+    Intro,
     Menu,
+    LevelSelect,
+    Controls,
+    Settings,
     Game,
     Pause,
     Win,
+    Lose,
+    Benchmark,
+    Credits,
+    Statistics,
+    /// An asset failed to load; shows what and offers a retry.
+    LoadError,
 }
 
 #[derive(Event)]
 struct CollisionWith(Entity);
 
+/// Atom detection and collection on the fixed step, after Tnua's ground
+/// sensor has been refreshed. Whatever counts pickups in the same step runs
+/// after it.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+struct AtomCollectionSet;
+
 #[derive(Event)]
 struct GameOver(GameState);
 
+/// Why the last run was lost, for the lose screen.
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq)]
+enum LoseCause {
+    #[default]
+    Fell,
+    Caught,
+    Hazard,
+}
+
+/// Sent once for each atom the player collects, with enough of it to put
+/// it back.
+#[derive(Event, Clone)]
+struct AtomCollected {
+    position: Vec3,
+    value: u32,
+    /// For a bonus atom, which doesn't count towards the objective, its
+    /// timer as it was.
+    bonus: Option<TimedAtom>,
+    moving: Option<MovingAtom>,
+}
+
+/// A game over that has been triggered but not yet shown, e.g. while the
+/// player is still being flung by the win blast.
+#[derive(Resource)]
+struct PendingGameOver {
+    state: GameState,
+    timer: Timer,
+}
+
 #[derive(Component)]
 struct WinGame;
 
+/// Ends the run when the player touches it.
+#[derive(Component)]
+struct Hazard;
+
+/// Everything spawned for a run that has to go when the run is torn down.
+#[derive(Component)]
+struct LevelEntity;
+
+/// Points an atom is worth when collected.
+#[derive(Component)]
+struct AtomValue(u32);
+
+/// The player's ball.
+#[derive(Component)]
+struct Player;
+
+/// Both players and the game camera as they were when the game was paused,
+/// put back exactly on resume.
+#[derive(Resource)]
+struct PauseSnapshot {
+    players: Vec<(Entity, Transform, LinearVelocity, AngularVelocity)>,
+    camera: Transform,
+}
+
 #[derive(Component)]
 struct MenuCamera;
 
 #[derive(Component)]
 struct StartButton;
 
+/// On the win screen beside "Play Again", which keeps the layout.
+#[derive(Component)]
+struct NewLayoutButton;
+
+/// Whether the next level gets a fresh `LevelConfig::seed`. Otherwise a
+/// replay keeps the same layout, for practising it.
+#[derive(Resource, Default)]
+struct NewLayout(bool);
+
 #[derive(Component)]
 struct QuitButton;
 
+#[derive(Component, Clone, Copy)]
+enum CreditsMenuAction {
+    Open,
+    Back,
+}
+
 #[derive(Component)]
 struct Menu {
     show_state: GameState,
@@ -151,6 +657,48 @@ struct StartInvisible {
 #[derive(Component)]
 struct DeathCountText;
 
+#[derive(Component)]
+struct PauseMenu;
+
+#[derive(Component)]
+struct LoseTitleText;
+
+#[derive(Component)]
+struct LoseSummaryText;
+
+#[derive(Component, Default)]
+struct Sprint(bool);
+
+/// Counts the player's jumps since they last touched the ground.
+#[derive(Component)]
+struct JumpState {
+    /// Jumps allowed before landing, the one off the ground included. Walking
+    /// off a ledge uses up the ground jump.
+    max_jumps: usize,
+    air_actions: TnuaSimpleAirActionsCounter,
+    /// A recent jump press, fed to Tnua as if the key were still held until
+    /// it runs out.
+    buffer: Option<Timer>,
+}
+
+/// The dash cooldown, and whether the dash key has been pressed since the
+/// last fixed step.
+#[derive(Component, Default)]
+struct DashState {
+    cooldown: Timer,
+    requested: bool,
+}
+
+impl Default for JumpState {
+    fn default() -> Self {
+        Self {
+            max_jumps: 1,
+            air_actions: TnuaSimpleAirActionsCounter::default(),
+            buffer: None,
+        }
+    }
+}
+
 use std::time::Duration;
 
 #[derive(Component)]
@@ -158,10 +706,7 @@ struct TextTime {
     timer: Timer,
 }
 
-fn text_timer(
-    mut q: Query<(&mut Visibility, &mut TextTime)>,
-    time: Res<Time>,
-) {
+fn text_timer(mut q: Query<(&mut Visibility, &mut TextTime)>, time: Res<Time>) {
     for (mut viz, mut text_timer) in q.iter_mut() {
         text_timer.timer.tick(time.delta());
 
@@ -178,10 +723,20 @@ fn game_camera(
 ) {
     let game_cam: bool = match state.get() {
         GameState::Loading => false,
+        GameState::Intro => false,
         GameState::Menu => false,
+        GameState::LevelSelect => false,
+        GameState::Controls => false,
+        GameState::Settings => false,
         GameState::Game => true,
         GameState::Win => false,
-        GameState::Pause => false,
+        GameState::Lose => false,
+        GameState::Benchmark => false,
+        GameState::Credits => false,
+        GameState::Statistics => false,
+        GameState::LoadError => false,
+        // Kept in view behind the pause menu.
+        GameState::Pause => true,
     };
 
     if let Ok(mut menu_cam) = menu_cam_query.single_mut() {
@@ -189,10 +744,47 @@ fn game_camera(
     };
     if let Ok((mut cam, mut t_cam)) = game_cam_query.single_mut() {
         cam.is_active = game_cam;
-        t_cam.cursor_lock_active = game_cam;
+        t_cam.cursor_lock_active = *state.get() == GameState::Game;
     };
 }
 
+fn apply_camera_config(
+    camera_config: Res<CameraConfig>,
+    mut cam_query: Query<(&mut ThirdPersonCamera, &mut Projection)>,
+) {
+    for (mut cam, mut projection) in cam_query.iter_mut() {
+        cam.sensitivity = Vec2::splat(camera_config.sensitivity);
+        cam.zoom = camera_config.zoom();
+        *projection = camera_config.projection();
+    }
+}
+
+/// Replaces the camera plugin's own scroll zoom, which only runs while the
+/// cursor is locked and isn't saved.
+fn scroll_zoom(mut scroll: EventReader<MouseWheel>, mut camera_config: ResMut<CameraConfig>) {
+    let lines: f32 = scroll
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            // Roughly one notch of a wheel.
+            MouseScrollUnit::Pixel => event.y / 100.0,
+        })
+        .sum();
+    if lines != 0.0 {
+        camera_config.adjust_zoom(1.0 - lines * 0.1);
+    }
+}
+
+fn apply_physics_feel(
+    feel: Res<PhysicsFeelConfig>,
+    mut player_query: Query<(&mut Friction, &mut Restitution), With<Player>>,
+) {
+    for (mut friction, mut restitution) in player_query.iter_mut() {
+        *friction = Friction::new(feel.friction);
+        *restitution = Restitution::new(feel.restitution);
+    }
+}
+
 fn show_menu(mut menu: Query<(&mut Visibility, &Menu)>, state: Res<State<GameState>>) {
     for (mut menu_viz, menu_type) in menu.iter_mut() {
         if menu_type.show_state == *state.get() {
@@ -203,21 +795,43 @@ fn show_menu(mut menu: Query<(&mut Visibility, &Menu)>, state: Res<State<GameSta
     }
 }
 
-fn setup_camera_and_lights(mut commands: Commands) {
-    commands.spawn((
-        Camera {
-            clear_color: ClearColorConfig::Custom(Color::from(css::DARK_GRAY)),
-            ..default()
-        },
-        Camera3d::default(),
-        RenderLayers::layer(0),
-        ThirdPersonCamera {
-            offset: Offset::new(2.0, 0.0),
-            cursor_lock_toggle_enabled: true,
-            cursor_lock_key: KeyCode::KeyC,
-            ..default()
-        },
-    ));
+/// Frees the cursor from the game camera, or holds it free in hold mode.
+const CURSOR_LOCK_KEY: KeyCode = KeyCode::KeyC;
+
+fn setup_camera_and_lights(
+    mut commands: Commands,
+    camera_config: Res<CameraConfig>,
+    env_assets: Res<EnvAssets>,
+    sky_config: Res<SkyConfig>,
+    pause_menu: Query<Entity, With<PauseMenu>>,
+) {
+    let camera = commands
+        .spawn((
+            Camera {
+                clear_color: ClearColorConfig::Custom(Color::from(css::DARK_GRAY)),
+                ..default()
+            },
+            Camera3d::default(),
+            camera_config.projection(),
+            RenderLayers::layer(0),
+            ThirdPersonCamera {
+                offset: Offset::new(2.0, 0.0),
+                cursor_lock_toggle_enabled: true,
+                cursor_lock_key: CURSOR_LOCK_KEY,
+                sensitivity: Vec2::splat(camera_config.sensitivity),
+                zoom_enabled: false,
+                zoom: camera_config.zoom(),
+                ..default()
+            },
+            env_assets.skybox(&sky_config),
+        ))
+        .id();
+
+    // The menu camera is off while paused, so the pause menu draws over the
+    // game instead.
+    for menu in pause_menu.iter() {
+        commands.entity(menu).insert(UiTargetCamera(camera));
+    }
 
     commands.spawn((PointLight::default(), Transform::from_xyz(5.0, 5.0, 5.0)));
 
@@ -227,72 +841,137 @@ fn setup_camera_and_lights(mut commands: Commands) {
             shadows_enabled: true,
             ..Default::default()
         },
+        ShadowLight,
+        Sun,
         Transform::default().looking_to(-Vec3::Y, Vec3::Z),
     ));
 }
 
-fn start_button_system(
+/// Hover and press feedback shared by every menu button. What a press does
+/// is left to each button's own system.
+fn button_visuals(
+    theme: Res<ColorTheme>,
     mut interaction_query: Query<
-        (
-            &Interaction,
-            &mut BackgroundColor,
-            &mut BorderColor,
-            &Children,
-        ),
-        (Changed<Interaction>, With<Button>, With<StartButton>),
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<Button>),
     >,
-    mut text_query: Query<&mut Text>,
-    mut next_state: ResMut<NextState<GameState>>,
 ) {
-    for (interaction, mut color, mut border_color, children) in &mut interaction_query {
-        let _text = text_query.get_mut(children[0]).unwrap();
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        let (background, border) = theme.button(*interaction);
+        *color = background.into();
+        border_color.0 = border;
+    }
+}
+
+/// Minimum time between hover blips, so sweeping the cursor across a column
+/// of buttons doesn't stack them up.
+const HOVER_SOUND_INTERVAL: Duration = Duration::from_millis(80);
+
+#[derive(Default)]
+struct ButtonSoundState {
+    last_hover: Option<Duration>,
+    /// A pressed button turns back to hovered on release, which shouldn't
+    /// blip again.
+    pressed: Option<Entity>,
+}
+
+fn button_sounds(
+    mut commands: Commands,
+    mut state: Local<ButtonSoundState>,
+    interaction_query: Query<(Entity, &Interaction), (Changed<Interaction>, With<Button>)>,
+    sound_assets: Res<SoundAssets>,
+    time: Res<Time<Real>>,
+) {
+    for (entity, interaction) in &interaction_query {
         match *interaction {
             Interaction::Pressed => {
-                *color = PRESSED_BUTTON.into();
-                border_color.0 = css::RED.into();
-                next_state.set(GameState::Game);
+                state.pressed = Some(entity);
+                commands.spawn(
+                    SamplePlayer::new(sound_assets.click.clone())
+                        .with_volume(Volume::Decibels(-10.0)),
+                );
             }
             Interaction::Hovered => {
-                *color = HOVERED_BUTTON.into();
-                border_color.0 = Color::WHITE;
+                if state.pressed.take() == Some(entity) {
+                    continue;
+                }
+                let now = time.elapsed();
+                if state
+                    .last_hover
+                    .is_some_and(|last| now - last < HOVER_SOUND_INTERVAL)
+                {
+                    continue;
+                }
+                state.last_hover = Some(now);
+                commands.spawn(
+                    SamplePlayer::new(sound_assets.hover.clone())
+                        .with_volume(Volume::Decibels(-14.0)),
+                );
             }
             Interaction::None => {
-                *color = NORMAL_BUTTON.into();
-                border_color.0 = Color::BLACK;
+                if state.pressed == Some(entity) {
+                    state.pressed = None;
+                }
             }
         }
     }
 }
 
+fn start_button_system(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<Button>, With<StartButton>)>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for interaction in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        next_state.set(GameState::Game);
+    }
+}
+
+fn new_layout_button_system(
+    interaction_query: Query<
+        &Interaction,
+        (Changed<Interaction>, With<Button>, With<NewLayoutButton>),
+    >,
+    mut new_layout: ResMut<NewLayout>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for interaction in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        new_layout.0 = true;
+        next_state.set(GameState::Game);
+    }
+}
+
 fn exit_button_system(
-    mut interaction_query: Query<
-        (
-            &Interaction,
-            &mut BackgroundColor,
-            &mut BorderColor,
-            &Children,
-        ),
-        (Changed<Interaction>, With<Button>, With<QuitButton>),
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<Button>, With<QuitButton>)>,
+    mut quit_confirm: ResMut<QuitConfirm>,
+) {
+    for interaction in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        quit_confirm.open = true;
+    }
+}
+
+fn credits_button_system(
+    interaction_query: Query<
+        (&Interaction, &CreditsMenuAction),
+        (Changed<Interaction>, With<Button>),
     >,
-    mut text_query: Query<&mut Text>,
-    mut exit: EventWriter<AppExit>,
+    mut next_state: ResMut<NextState<GameState>>,
 ) {
-    for (interaction, mut color, mut border_color, children) in &mut interaction_query {
-        let _text = text_query.get_mut(children[0]).unwrap();
-        match *interaction {
-            Interaction::Pressed => {
-                *color = PRESSED_BUTTON.into();
-                border_color.0 = css::RED.into();
-                exit.write(AppExit::Success);
-            }
-            Interaction::Hovered => {
-                *color = HOVERED_BUTTON.into();
-                border_color.0 = Color::WHITE;
-            }
-            Interaction::None => {
-                *color = NORMAL_BUTTON.into();
-                border_color.0 = Color::BLACK;
-            }
+    for (interaction, action) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match *action {
+            CreditsMenuAction::Open => next_state.set(GameState::Credits),
+            CreditsMenuAction::Back => next_state.set(GameState::Menu),
         }
     }
 }
@@ -313,193 +992,1299 @@ fn setup_menu(mut commands: Commands, font_assets: Res<FontAssets>) {
         Visibility::Hidden,
     ));
 
+    commands.spawn((
+        lose_menu(&font_assets),
+        RenderLayers::layer(1),
+        Visibility::Hidden,
+    ));
+
     commands.spawn((
         pause_menu(&font_assets),
         RenderLayers::layer(1),
         Visibility::Hidden,
     ));
+
+    commands.spawn((
+        credits_menu(&font_assets),
+        RenderLayers::layer(1),
+        Visibility::Hidden,
+    ));
 }
 
+const WALL_THICKNESS: f32 = 4.0;
+
 fn setup_level(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     atom_assets: Res<AtomAssets>,
+    settings: Res<Settings>,
+    levels: Res<Levels>,
+    selected: Res<SelectedLevel>,
+    mut level_config: ResMut<LevelConfig>,
+    mut new_layout: ResMut<NewLayout>,
+    respawn: Res<RespawnConfig>,
+    chaser_assets: Res<ChaserAssets>,
+    chaser_config: Res<ChaserConfig>,
+    bonus_config: Res<BonusAtomConfig>,
+    difficulty: Res<DifficultyConfig>,
+    ground_assets: Res<GroundAssets>,
 ) {
+    if std::mem::take(&mut new_layout.0) {
+        level_config.seed = rand::random();
+    }
+    let level = selected.get(&levels);
+    let tuning = difficulty.tuning(settings.difficulty);
+
     commands.spawn((
-        Mesh3d(meshes.add(Plane3d::default().mesh().size(1024.0, 1024.0))),
-        MeshMaterial3d(materials.add(Color::WHITE)),
+        Mesh3d(
+            meshes.add(
+                Plane3d::default()
+                    .mesh()
+                    .size(level.ground_size, level.ground_size),
+            ),
+        ),
+        MeshMaterial3d(materials.add(ground_assets.material(level.ground_size))),
         RigidBody::Static,
         Collider::half_space(Vec3::Y),
+        LevelEntity,
     ));
 
-    let start_vec = Vec3::new(60.0, 4.0, 0.0);
-
-    for i in -20..20 {
-        for j in -20..20 {
-            let sphere_vec = Vec3::new(60.0 + 9.0 * j as f32, 4.0, 9.0 * i as f32);
-            let distance = sphere_vec.distance(start_vec);
-            if distance < 30.0 {
-                commands.spawn((
-                    SceneRoot(atom_assets.u_atom.clone()),
-                    Transform::from_translation(sphere_vec).looking_to(Vec3::Z, Vec3::Y),
-                    RigidBody::Static,
-                    Collider::sphere(4.0),
-                    WinGame,
-                ));
-            }
+    // Barely-there glass, so the edge is visible up close without boxing in
+    // the view.
+    let wall_distance = level_config.wall_distance(level);
+    let wall_material = materials.add(StandardMaterial {
+        base_color: Color::srgba(0.6, 0.8, 1.0, 0.08),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+    // Long enough to close off the corners.
+    let wall_size = Vec3::new(
+        (wall_distance + WALL_THICKNESS) * 2.0,
+        level_config.wall_height,
+        WALL_THICKNESS,
+    );
+    let wall_mesh = meshes.add(Cuboid::from_size(wall_size));
+    for side in [Vec3::X, Vec3::NEG_X, Vec3::Z, Vec3::NEG_Z] {
+        commands.spawn((
+            Mesh3d(wall_mesh.clone()),
+            MeshMaterial3d(wall_material.clone()),
+            Transform::from_translation(
+                side * (wall_distance + WALL_THICKNESS / 2.0)
+                    + Vec3::Y * level_config.wall_height / 2.0,
+            )
+            .looking_to(side, Vec3::Y),
+            RigidBody::Static,
+            Collider::cuboid(wall_size.x, wall_size.y, wall_size.z),
+            NotShadowCaster,
+            LevelEntity,
+        ));
+    }
+
+    let ramp_material = materials.add(Color::srgb(0.8, 0.8, 0.85));
+    for ramp in &level.ramps {
+        let size = ramp.size();
+        commands.spawn((
+            Mesh3d(meshes.add(Cuboid::from_size(size))),
+            MeshMaterial3d(ramp_material.clone()),
+            ramp.transform(),
+            RigidBody::Static,
+            Collider::cuboid(size.x, size.y, size.z),
+            LevelEntity,
+        ));
+    }
+
+    let positions = level
+        .atoms
+        .positions(&level_config, tuning, respawn.spawn_point);
+
+    // Drawn from their own stream, so adding hazards to a level doesn't
+    // change which of its atoms move.
+    let mut hazard_rng = StdRng::seed_from_u64(level_config.seed.wrapping_add(2));
+    let (hazards, atoms): (Vec<Vec3>, Vec<Vec3>) = positions
+        .iter()
+        .partition(|_| hazard_rng.random::<f32>() < level.hazard_fraction);
+
+    let hazard_mesh = meshes.add(Sphere::new(4.0));
+    let hazard_material = materials.add(StandardMaterial {
+        base_color: css::RED.into(),
+        emissive: LinearRgba::rgb(2.0, 0.0, 0.0),
+        ..default()
+    });
+    for position in hazards {
+        commands.spawn((
+            Mesh3d(hazard_mesh.clone()),
+            MeshMaterial3d(hazard_material.clone()),
+            Transform::from_translation(position),
+            RigidBody::Static,
+            Collider::sphere(4.0),
+            // Bumping into the side counts too, not just landing on top.
+            CollisionEventsEnabled,
+            Hazard,
+            LevelEntity,
+        ));
+    }
+
+    // Attracted atoms are already on the move.
+    let mut rng = StdRng::seed_from_u64(level_config.seed);
+    for sphere_vec in atoms.iter().copied() {
+        let atom = spawn_atom(&mut commands, &atom_assets, &settings, sphere_vec);
+        if !settings.atom_attraction && rng.random::<f32>() < level_config.moving_fraction {
+            let heading = rng.random_range(0.0..std::f32::consts::TAU);
+            commands.entity(atom).insert((
+                MovingAtom::new(
+                    sphere_vec,
+                    Vec3::new(heading.cos(), 0.0, heading.sin()),
+                    level_config.moving_speed,
+                    level_config.moving_range,
+                    rng.random_range(0.0..std::f32::consts::TAU),
+                ),
+                RigidBody::Kinematic,
+            ));
         }
     }
+
+    spawn_bonus_atoms(
+        &mut commands,
+        &atom_assets,
+        &settings,
+        &bonus_config,
+        level_config.seed,
+        respawn.spawn_point,
+        &positions,
+    );
+
+    spawn_chasers(
+        &mut commands,
+        &chaser_assets,
+        &chaser_config,
+        &settings,
+        respawn.spawn_point,
+    );
+
+    commands.insert_resource(Objective::new(atoms.len() as u32, tuning.critical_mass));
+}
+
+fn spawn_atom(
+    commands: &mut Commands,
+    atom_assets: &AtomAssets,
+    settings: &Settings,
+    position: Vec3,
+) -> Entity {
+    let pass_through = settings.atom_pickup == AtomPickup::PassThrough;
+
+    // Sensors have no contact response, so attracting atoms that the player
+    // passes through are kinematic to keep them from sinking into the ground.
+    let atom_body = match (settings.atom_attraction, pass_through) {
+        (true, false) => RigidBody::Dynamic,
+        (true, true) => RigidBody::Kinematic,
+        (false, _) => RigidBody::Static,
+    };
+
+    let mut atom = commands.spawn((
+        SceneRoot(atom_assets.u_atom.clone()),
+        Transform::from_translation(position).looking_to(Vec3::Z, Vec3::Y),
+        atom_body,
+        Collider::sphere(4.0),
+        WinGame,
+        AtomValue(1),
+        LevelEntity,
+    ));
+    if pass_through {
+        atom.insert((Sensor, CollisionEventsEnabled));
+    }
+    atom.id()
+}
+
+fn teardown_level(
+    mut commands: Commands,
+    query: Query<
+        Entity,
+        Or<(
+            With<LevelEntity>,
+            With<ThirdPersonCameraTarget>,
+            With<SecondPlayer>,
+        )>,
+    >,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
 }
 
 fn setup_player(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    respawn: Res<RespawnConfig>,
+    light_config: Res<PlayerLightConfig>,
+    theme: Res<ColorTheme>,
+    feel: Res<PhysicsFeelConfig>,
+    settings: Res<Settings>,
+    difficulty: Res<DifficultyConfig>,
+    players: Res<Players>,
+    coop_config: Res<CoopConfig>,
 ) {
+    let mut ball = |color: Color, position: Vec3| {
+        (
+            Mesh3d(meshes.add(Sphere { radius: 0.5 })),
+            MeshMaterial3d(materials.add(color)),
+            Transform::from_translation(position).looking_to(Vec3::X, Vec3::Y),
+            RigidBody::Dynamic,
+            Collider::sphere(0.5),
+            Friction::new(feel.friction),
+            Restitution::new(feel.restitution),
+            TnuaController::default(),
+            TnuaAvian3dSensorShape(Collider::cylinder(0.7, 0.0)),
+            Sprint::default(),
+            JumpState::default(),
+            DashState::default(),
+            WallContact::default(),
+            CollidingEntities::default(),
+            children![player_light(&light_config, &settings, color)],
+        )
+    };
+
     commands.spawn((
-        Mesh3d(meshes.add(Sphere { radius: 0.5 })),
-        MeshMaterial3d(materials.add(Color::from(css::DARK_CYAN))),
-        Transform::from_xyz(0.0, 4.0, 0.0).looking_to(Vec3::X, Vec3::Y),
-        RigidBody::Dynamic,
-        Collider::sphere(0.5),
-        TnuaController::default(),
-        TnuaAvian3dSensorShape(Collider::cylinder(0.7, 0.0)),
+        ball(theme.player, respawn.spawn_point),
         ThirdPersonCameraTarget,
+        Player,
+        ComboShield::default(),
+        Health(difficulty.tuning(settings.difficulty).health),
     ));
+    if players.0 == 2 {
+        commands.spawn((
+            ball(
+                coop_config.color,
+                respawn.spawn_point + coop_config.spawn_offset,
+            ),
+            SecondPlayer,
+        ));
+    }
 }
 
-fn detect_atom(
-    query: Query<&TnuaProximitySensor>,
-    mut event_collision: EventWriter<CollisionWith>,
+fn respawn_fallen_player(
+    mut query: Query<
+        (
+            &mut Position,
+            &mut Transform,
+            &mut LinearVelocity,
+            &mut AngularVelocity,
+        ),
+        AnyPlayer,
+    >,
+    respawn: Res<RespawnConfig>,
+    settings: Res<Settings>,
+    run_timer: Res<RunTimer>,
+    pending: Option<Res<PendingGameOver>>,
+    mut event_game_over: EventWriter<GameOver>,
+    mut lose_cause: ResMut<LoseCause>,
 ) {
-    let Ok(sensor) = query.single() else {
-        return;
-    };
-
-    let Some(output) = &sensor.output else {
+    if pending.is_some() {
         return;
-    };
+    }
 
-    let entity2 = output.entity;
+    for (mut position, mut transform, mut linear, mut angular) in query.iter_mut() {
+        if position.y >= respawn.fall_threshold {
+            continue;
+        }
 
-    event_collision.write(CollisionWith(entity2));
+        if settings.lose_on_fall && run_timer.running {
+            *lose_cause = LoseCause::Fell;
+            event_game_over.write(GameOver(GameState::Lose));
+            continue;
+        }
 
-    println!("Player and {entity2} colliding");
+        position.0 = respawn.spawn_point;
+        transform.translation = respawn.spawn_point;
+        linear.0 = Vec3::ZERO;
+        angular.0 = Vec3::ZERO;
+    }
 }
 
-fn collision_response(
-    mut event_collision: EventReader<CollisionWith>,
-    mut event_game_over: EventWriter<GameOver>,
-    query: Query<&WinGame>,
+fn detect_atom(
+    query: Query<&TnuaProximitySensor, AnyPlayer>,
+    entities: &Entities,
+    mut event_collision: EventWriter<CollisionWith>,
 ) {
-    for ev in event_collision.read() {
-        eprintln!("Entity {:?} Collide!", &ev.0);
-        if query.contains(ev.0) {
-            event_game_over.write(GameOver(GameState::Win));
+    for sensor in query.iter() {
+        let Some(output) = &sensor.output else {
+            continue;
+        };
+
+        let entity2 = output.entity;
+
+        // Anything despawned between fixed steps can still be in the sensor's
+        // last reading.
+        if !entities.contains(entity2) {
+            continue;
         }
+
+        event_collision.write(CollisionWith(entity2));
     }
 }
 
-fn end_game(
-    player: Single<Entity, With<ThirdPersonCameraTarget>>,
-    mut commands: Commands,
-    mut event_game_over: EventReader<GameOver>,
-    mut next_state: ResMut<NextState<GameState>>,
-    sound_assets: Res<SoundAssets>,
-    mut viz_timers: Query<(Entity, &mut Visibility, &StartInvisible)>,
-    mut text_query: Single<&mut Text, With<DeathCountText>>,
-
+// Tnua's ground sensor ignores `Sensor` colliders, so pass-through atoms are
+// picked up from physics overlap events instead.
+fn detect_atom_overlap(
+    mut started: EventReader<CollisionStarted>,
+    player: Query<(), AnyPlayer>,
+    mut event_collision: EventWriter<CollisionWith>,
 ) {
-    let Some(ev) = event_game_over.read().last() else {
+    for CollisionStarted(a, b) in started.read() {
+        let other = if player.contains(*a) {
+            *b
+        } else if player.contains(*b) {
+            *a
+        } else {
+            continue;
+        };
+
+        event_collision.write(CollisionWith(other));
+    }
+}
+
+/// Screen shake added for each collected atom. The final blast shakes at
+/// full strength.
+const ATOM_TRAUMA: f32 = 0.3;
+
+/// Each pickup sound is played up to this much faster or slower, so long
+/// chains don't repeat the exact same note.
+const PICKUP_PITCH_SPREAD: f64 = 0.1;
+/// Pitch rise for each atom in the current combo, so a combo climbs.
+const PICKUP_PITCH_STEP: f64 = 0.03;
+/// The climb stops here, before the sound gets shrill.
+const PICKUP_PITCH_MAX_RISE: f64 = 0.3;
+
+/// Playback speed for the `combo`th pickup in a row, where 1 is normal.
+fn pickup_pitch(combo: u32) -> f64 {
+    let rise = (f64::from(combo) * PICKUP_PITCH_STEP).min(PICKUP_PITCH_MAX_RISE);
+    1.0 + rise + rand::rng().random_range(-PICKUP_PITCH_SPREAD..PICKUP_PITCH_SPREAD)
+}
+
+fn collision_response(
+    mut commands: Commands,
+    mut event_collision: EventReader<CollisionWith>,
+    mut event_game_over: EventWriter<GameOver>,
+    mut event_collected: EventWriter<AtomCollected>,
+    mut objective: ResMut<Objective>,
+    mut atoms_collected: ResMut<Collected>,
+    mut unsaved_atoms: ResMut<UnsavedAtoms>,
+    sound_assets: Res<SoundAssets>,
+    capture_config: Res<CaptureConfig>,
+    chain_config: Res<ChainConfig>,
+    mut chain: ResMut<ChainReaction>,
+    mut shake: ResMut<ScreenShake>,
+    combo: Res<Combo>,
+    query: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &AtomValue,
+            Option<&Capture>,
+            Has<ChainFuse>,
+            Option<&TimedAtom>,
+            Option<&MovingAtom>,
+        ),
+        With<WinGame>,
+    >,
+    detonators: Query<(), With<Detonator>>,
+) {
+    let mut collected = Vec::new();
+
+    for ev in event_collision.read() {
+        if detonators.contains(ev.0) {
+            event_game_over.write(GameOver(GameState::Win));
+            continue;
+        }
+
+        if collected.contains(&ev.0) {
+            continue;
+        }
+        let Ok((_, transform, value, capture, chained, bonus, moving)) = query.get(ev.0) else {
+            continue;
+        };
+        if !chained && !capture_config.allows_collection(capture) {
+            continue;
+        }
+
+        debug!("Player collected atom {}", ev.0);
+        commands.entity(ev.0).despawn();
+        // The combo only counts these once they're sent, so the ones
+        // collected this frame are added on.
+        let pitch = PlaybackSettings {
+            speed: pickup_pitch(combo.count + collected.len() as u32),
+            ..default()
+        };
+        if chained {
+            commands.spawn((
+                SamplePlayer::new(sound_assets.u_atom.clone()).with_volume(Volume::Decibels(-12.0)),
+                pitch,
+            ));
+        } else {
+            commands.spawn((
+                SamplePlayer::new(sound_assets.pickup.clone()).with_volume(Volume::Decibels(-6.0)),
+                pitch,
+            ));
+        }
+        collected.push(ev.0);
+        shake.add(ATOM_TRAUMA);
+        if bonus.is_none() {
+            objective.collect();
+            atoms_collected.0 += 1;
+        }
+        unsaved_atoms.0 += 1;
+        let position = transform.translation();
+        event_collected.write(AtomCollected {
+            position,
+            value: value.0,
+            bonus: bonus.cloned(),
+            moving: moving.cloned(),
+        });
+        if chained {
+            chain.length += 1;
+            chain.front = position;
+        }
+
+        // Set off the neighbours, which will come back through here once
+        // their fuses burn down.
+        for (atom, neighbour, _, _, already_chained, ..) in query.iter() {
+            if already_chained
+                || collected.contains(&atom)
+                || neighbour.translation().distance(position) >= chain_config.radius
+            {
+                continue;
+            }
+            commands.entity(atom).insert(ChainFuse::new(&chain_config));
+        }
+    }
+}
+
+/// Once the run is over, e.g. while flung by the win blast, landing on a
+/// hazard changes nothing.
+fn hit_hazard(
+    mut commands: Commands,
+    mut event_collision: EventReader<CollisionWith>,
+    mut event_game_over: EventWriter<GameOver>,
+    mut lose_cause: ResMut<LoseCause>,
+    pending: Option<Res<PendingGameOver>>,
+    invincible_config: Res<InvincibleConfig>,
+    hazards: Query<(), With<Hazard>>,
+    mut player: Query<(Entity, &mut Health, Has<Invincible>), With<Player>>,
+) {
+    // Counted rather than `any`, which would stop early and leave the rest
+    // to be read again next frame.
+    let hits = event_collision
+        .read()
+        .filter(|ev| hazards.contains(ev.0))
+        .count();
+    let Ok((player, mut health, invincible)) = player.single_mut() else {
         return;
     };
-    commands.spawn(SamplePlayer::new(sound_assets.u_atom.clone()));
+    if hits == 0 || invincible || pending.is_some() {
+        return;
+    }
+
+    // One hit per contact, however many hazards were touched at once.
+    health.0 = health.0.saturating_sub(1);
+    if health.0 == 0 {
+        *lose_cause = LoseCause::Hazard;
+        event_game_over.write(GameOver(GameState::Lose));
+    } else {
+        commands
+            .entity(player)
+            .insert(Invincible::new(&invincible_config));
+    }
+}
 
+fn end_game(
+    player: Single<(Entity, &GlobalTransform), With<ThirdPersonCameraTarget>>,
+    mut commands: Commands,
+    mut event_game_over: EventReader<GameOver>,
+    mut run_timer: ResMut<RunTimer>,
+    mut stats: ResMut<Stats>,
+    pending: Option<Res<PendingGameOver>>,
+    sound_assets: Res<SoundAssets>,
+    blast: Res<BlastConfig>,
+    mut shake: ResMut<ScreenShake>,
+    detonators: Query<&GlobalTransform, With<Detonator>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    shockwave: Res<ShockwaveConfig>,
+    settings: Res<Settings>,
+) {
+    let Some(ev) = event_game_over.read().last() else {
+        return;
+    };
+    let state = ev.0.clone();
+    event_game_over.clear();
+
+    if pending.is_some() {
+        return;
+    }
+
+    run_timer.running = false;
+    stats.runs += 1;
+
+    let mut delay = Duration::ZERO;
+
+    if state == GameState::Win {
+        commands.spawn(SamplePlayer::new(sound_assets.u_atom.clone()));
+        shake.add(1.0);
+
+        let (entity, transform) = *player;
+        let position = transform.translation();
+        let center = detonators
+            .single()
+            .map(|detonator| detonator.translation())
+            .unwrap_or(position - Vec3::Y);
+        spawn_shockwave(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            &shockwave,
+            &settings,
+            center,
+            blast.radius,
+        );
+
+        let offset = position - center;
+        let falloff = (1.0 - offset.length() / blast.radius).clamp(0.25, 1.0);
+        let direction = (offset.normalize_or_zero() + Vec3::Y).normalize();
+
+        let mut rng = rand::rng();
+        let spin = Vec3::new(
+            rng.random_range(-1.0..1.0),
+            rng.random_range(-1.0..1.0),
+            rng.random_range(-1.0..1.0),
+        ) * 20.0;
+
+        // Hand the ball over to the physics engine so it tumbles freely.
+        commands.entity(entity).insert((
+            TnuaToggle::Disabled,
+            LinearVelocity(direction * blast.launch_speed * falloff),
+            AngularVelocity(spin),
+        ));
+
+        delay = Duration::from_secs_f32(blast.fling_secs);
+    }
+
+    commands.insert_resource(PendingGameOver {
+        state,
+        timer: Timer::new(delay, TimerMode::Once),
+    });
+}
+
+fn finish_game_over(
+    player: Single<Entity, With<ThirdPersonCameraTarget>>,
+    mut commands: Commands,
+    mut pending: ResMut<PendingGameOver>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut viz_timers: Query<(Entity, &mut Visibility, &StartInvisible)>,
+    mut text_query: Single<&mut Text, With<DeathCountText>>,
+    combo: Res<Combo>,
+    objective: Res<Objective>,
+    time: Res<Time>,
+) {
+    pending.timer.tick(time.delta());
+    if !pending.timer.finished() {
+        return;
+    }
+
+    commands.remove_resource::<PendingGameOver>();
     commands.entity(*player).despawn();
-    next_state.set(ev.0.clone());
+    next_state.set(pending.state.clone());
 
     let number = if let Ok(normal) = Normal::new(240000.0, 55000.0) {
-        rand::rng().sample(normal) as u32
+        rand::rng().sample(normal) as u64
     } else {
         237559
     };
+    // A run that just reaches critical mass with no combos gets the base
+    // tally; combos push it up.
+    let number = number * u64::from(combo.score.max(1)) / u64::from(objective.required.max(1));
 
-    text_query.0 = format!("{} Civilian Deaths", number.to_string());  
+    text_query.0 = format!("{number} Civilian Deaths");
 
     for (id, mut viz, time) in viz_timers.iter_mut() {
         *viz = Visibility::Hidden;
         commands.entity(id).insert((TextTime {
-                timer: Timer::new(time.time, TimerMode::Once),
-            },));
+            timer: Timer::new(time.time, TimerMode::Once),
+        },));
     }
+}
 
-    event_game_over.clear();
+fn lose_summary_text(
+    objective: Res<Objective>,
+    lose_cause: Res<LoseCause>,
+    mut title_query: Query<&mut Text, (With<LoseTitleText>, Without<LoseSummaryText>)>,
+    mut text_query: Query<&mut Text, With<LoseSummaryText>>,
+) {
+    let (title, ending) = match *lose_cause {
+        LoseCause::Fell => ("You Fell!", "falling"),
+        LoseCause::Caught => ("Caught!", "getting caught"),
+        LoseCause::Hazard => ("Meltdown!", "touching a hazard"),
+    };
+
+    for mut text in title_query.iter_mut() {
+        text.0 = title.to_string();
+    }
+    for mut text in text_query.iter_mut() {
+        text.0 = format!(
+            "You collected {} of {} atoms before {ending}",
+            objective.collected, objective.required
+        );
+    }
+}
+
+fn key_pause(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard.just_pressed(bindings.pause) {
+        next_state.set(GameState::Pause);
+    }
 }
 
-fn key_pause(keyboard: Res<ButtonInput<KeyCode>>, mut next_state: ResMut<NextState<GameState>>) {
-    if keyboard.just_pressed(KeyCode::Escape) {
+/// Alt-tabbing away pauses, but coming back doesn't resume; the player
+/// does that themselves.
+fn pause_on_focus_loss(
+    mut focus_events: EventReader<WindowFocused>,
+    settings: Res<Settings>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let lost = focus_events.read().filter(|event| !event.focused).count() > 0;
+    if lost && settings.pause_on_focus_loss {
         next_state.set(GameState::Pause);
     }
 }
 
-fn key_unpause(keyboard: Res<ButtonInput<KeyCode>>, mut next_state: ResMut<NextState<GameState>>) {
-    if keyboard.just_pressed(KeyCode::Escape) {
+fn key_unpause(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard.just_pressed(bindings.pause) {
         next_state.set(GameState::Game);
     }
 }
 
-fn apply_controls(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&mut TnuaController, &GlobalTransform)>,
-    camera_query: Query<&GlobalTransform, With<ThirdPersonCamera>>,
-) {
-    let Ok((mut controller, player_transform)) = query.single_mut() else {
-        return;
-    };
-
-    let Ok(camera) = camera_query.single() else {
-        return;
-    };
-
-    let mut direction = Vec3::ZERO;
-
-    if keyboard.pressed(KeyCode::KeyW) {
-        direction += player_transform.forward().as_vec3();
-    }
-    if keyboard.pressed(KeyCode::KeyS) {
-        direction += player_transform.back().as_vec3();
-    }
-    if keyboard.pressed(KeyCode::KeyA) {
-        direction += player_transform.left().as_vec3();
-    }
-    if keyboard.pressed(KeyCode::KeyD) {
-        direction += player_transform.right().as_vec3();
-    }
-
-    controller.basis(TnuaBuiltinWalk {
-        desired_velocity: direction.normalize_or_zero() * 20.0,
-        desired_forward: Dir3::new(
-            camera.forward().as_vec3() - camera.forward().as_vec3().project_onto(Vec3::Y),
-        )
-        .ok(),
-
-        float_height: 4.0,
-        ..Default::default()
-    });
+fn key_leave_win(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard.just_pressed(bindings.pause) {
+        next_state.set(GameState::Menu);
+    }
+}
+
+// Tnua's pipeline (including `apply_controls`) is skipped while physics time
+// is paused, so the player's position, velocity and input all freeze with it.
+fn pause_physics(mut physics_time: ResMut<Time<Physics>>) {
+    physics_time.pause();
+}
+
+fn resume_physics(mut physics_time: ResMut<Time<Physics>>) {
+    physics_time.unpause();
+}
+
+fn take_pause_snapshot(
+    mut commands: Commands,
+    players: Query<(Entity, &Transform, &LinearVelocity, &AngularVelocity), AnyPlayer>,
+    camera: Query<&Transform, With<ThirdPersonCamera>>,
+) {
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    commands.insert_resource(PauseSnapshot {
+        players: players
+            .iter()
+            .map(|(entity, transform, linear, angular)| (entity, *transform, *linear, *angular))
+            .collect(),
+        camera: *camera,
+    });
+}
+
+// The camera stops reading mouse motion while the cursor is unlocked, and
+// would catch up on whatever was left over when it locks again, so that's
+// dropped along with any other drift.
+fn restore_pause_snapshot(
+    mut commands: Commands,
+    snapshot: Option<Res<PauseSnapshot>>,
+    mut players: Query<(&mut Transform, &mut LinearVelocity, &mut AngularVelocity), AnyPlayer>,
+    mut camera: Query<
+        &mut Transform,
+        (
+            With<ThirdPersonCamera>,
+            Without<Player>,
+            Without<SecondPlayer>,
+        ),
+    >,
+    mut mouse_motion: ResMut<Events<MouseMotion>>,
+) {
+    let Some(snapshot) = snapshot else {
+        return;
+    };
+    commands.remove_resource::<PauseSnapshot>();
+    mouse_motion.clear();
+
+    for (entity, transform, linear, angular) in &snapshot.players {
+        if let Ok((mut player_transform, mut player_linear, mut player_angular)) =
+            players.get_mut(*entity)
+        {
+            *player_transform = *transform;
+            *player_linear = *linear;
+            *player_angular = *angular;
+        }
+    }
+    if let Ok(mut camera_transform) = camera.single_mut() {
+        *camera_transform = snapshot.camera;
+    }
+}
+
+fn sprint_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    settings: Res<Settings>,
+    mut query: Query<&mut Sprint, With<Player>>,
+) {
+    for mut sprint in query.iter_mut() {
+        match settings.sprint_mode {
+            InputMode::Hold => sprint.0 = keyboard.pressed(bindings.sprint),
+            InputMode::Toggle => {
+                if keyboard.just_pressed(bindings.sprint) {
+                    sprint.0 = !sprint.0;
+                }
+            }
+        }
+    }
+}
+
+// The camera plugin only knows how to toggle the cursor lock, so hold mode
+// disables its toggle and keeps the lock and window cursor in sync here.
+fn cursor_lock_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    settings: Res<Settings>,
+    state: Res<State<GameState>>,
+    countdown: Res<Countdown>,
+    mut cam_query: Query<&mut ThirdPersonCamera>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut cam) = cam_query.single_mut() else {
+        return;
+    };
+
+    let toggle = settings.cursor_lock_mode == InputMode::Toggle;
+    if cam.cursor_lock_toggle_enabled != toggle {
+        cam.cursor_lock_toggle_enabled = toggle;
+    }
+    if toggle {
+        return;
+    }
+
+    if *state.get() == GameState::Game && countdown.finished() {
+        cam.cursor_lock_active = !keyboard.pressed(cam.cursor_lock_key);
+    }
+
+    if let Ok(mut window) = window_query.single_mut() {
+        if cam.cursor_lock_active {
+            window.cursor_options.grab_mode = CursorGrabMode::Locked;
+            window.cursor_options.visible = false;
+        } else {
+            window.cursor_options.grab_mode = CursorGrabMode::None;
+            window.cursor_options.visible = true;
+        }
+    }
+}
+
+fn apply_controls(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut query: Query<(
+        &mut TnuaController,
+        &mut JumpState,
+        &mut DashState,
+        &GlobalTransform,
+        &Sprint,
+        &WallContact,
+        Has<SecondPlayer>,
+    )>,
+    camera_query: Query<&GlobalTransform, With<ThirdPersonCamera>>,
+    coop_camera_query: Query<&GlobalTransform, With<CoopCamera>>,
+    countdown: Res<Countdown>,
+    movement: Res<MovementConfig>,
+    settings: Res<Settings>,
+    difficulty: Res<DifficultyConfig>,
+    time: Res<Time>,
+) {
+    // The walk basis is still fed during the countdown so Tnua picks up
+    // smoothly once the ball is unfrozen, but all input is ignored until "Go".
+    let pressed = |key| countdown.finished() && keyboard.pressed(key);
+
+    for (mut controller, mut jump_state, mut dash_state, player_transform, sprint, wall, second) in
+        query.iter_mut()
+    {
+        let camera = if second {
+            coop_camera_query.single()
+        } else {
+            camera_query.single()
+        };
+        let Ok(camera) = camera else {
+            continue;
+        };
+
+        let mut direction = Vec3::ZERO;
+
+        // Player two turns with the arrow keys, so only moves forward and
+        // back.
+        let (forward, back, left, right, jump) = if second {
+            (
+                coop::FORWARD_KEY,
+                coop::BACK_KEY,
+                None,
+                None,
+                coop::JUMP_KEY,
+            )
+        } else {
+            (
+                bindings.forward,
+                bindings.back,
+                Some(bindings.left),
+                Some(bindings.right),
+                bindings.jump,
+            )
+        };
+
+        if pressed(forward) {
+            direction += player_transform.forward().as_vec3();
+        }
+        if pressed(back) {
+            direction += player_transform.back().as_vec3();
+        }
+        if left.is_some_and(pressed) {
+            direction += player_transform.left().as_vec3();
+        }
+        if right.is_some_and(pressed) {
+            direction += player_transform.right().as_vec3();
+        }
+
+        let move_speed = movement.move_speed * difficulty.tuning(settings.difficulty).speed_factor;
+        let speed = if sprint.0 {
+            move_speed * movement.sprint_factor
+        } else {
+            move_speed
+        };
+
+        let camera_forward = Dir3::new(
+            camera.forward().as_vec3() - camera.forward().as_vec3().project_onto(Vec3::Y),
+        )
+        .ok();
+
+        controller.basis(TnuaBuiltinWalk {
+            desired_velocity: direction.normalize_or_zero() * speed,
+            desired_forward: camera_forward,
+
+            float_height: movement.float_height,
+            // Tnua times this from its own record of the last grounded frame,
+            // and a jump inside it counts the same as one from the ground.
+            coyote_time: movement.coyote_time.as_secs_f32(),
+            ..Default::default()
+        });
+
+        // Resets itself once the controller reports the player grounded.
+        jump_state.air_actions.update(&controller);
+
+        // A buffered jump that has just taken off with the key already released
+        // is fed for one more buffer length, about as long as a tap.
+        let held = pressed(jump);
+        if controller.action_flow_status().just_starting() == Some(TnuaBuiltinJump::NAME) {
+            if held {
+                jump_state.buffer = None;
+            } else if let Some(buffer) = jump_state.buffer.as_mut() {
+                buffer.reset();
+            }
+        }
+
+        // Tnua won't restart a jump that is still being fed, so holding the key
+        // through the first jump doesn't spend the second one.
+        if held || jump_state.buffer.is_some() {
+            controller.action(TnuaBuiltinJump {
+                height: movement.jump_height,
+                // Off a wall in reach, even with no air jumps left.
+                allow_in_air: jump_state.air_actions.air_count_for(TnuaBuiltinJump::NAME)
+                    < jump_state.max_jumps
+                    || wall.0.is_some(),
+                // Otherwise Tnua drops a buffered press it can't start in time.
+                input_buffer_time: movement.jump_buffer.as_secs_f32(),
+                ..Default::default()
+            });
+        }
+
+        // Tnua carries a dash through once it starts, so it only needs feeding
+        // the once. Without movement keys held, the dash goes where the camera
+        // faces.
+        dash_state.cooldown.tick(time.delta());
+        if std::mem::take(&mut dash_state.requested) && dash_state.cooldown.finished() {
+            let dash_direction = direction
+                .try_normalize()
+                .or(camera_forward.map(Vec3::from))
+                .unwrap_or_default();
+            controller.action(TnuaBuiltinDash {
+                displacement: dash_direction * movement.dash_distance,
+                desired_forward: camera_forward,
+                allow_in_air: true,
+                speed: movement.dash_speed,
+                brake_to_speed: speed,
+                ..Default::default()
+            });
+            dash_state.cooldown = Timer::new(movement.dash_cooldown, TimerMode::Once);
+        }
+
+        if jump_state
+            .buffer
+            .as_mut()
+            .is_some_and(|buffer| buffer.tick(time.delta()).finished())
+        {
+            jump_state.buffer = None;
+        }
+    }
+}
+
+/// Presses are caught here rather than in `apply_controls`, since a fixed
+/// step doesn't run every frame and would miss some of them.
+fn catch_presses(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    countdown: Res<Countdown>,
+    movement: Res<MovementConfig>,
+    mut players: Query<(&mut JumpState, &mut DashState, Has<SecondPlayer>)>,
+) {
+    if !countdown.finished() {
+        return;
+    }
+
+    for (mut jump_state, mut dash_state, second) in players.iter_mut() {
+        let jump = if second {
+            coop::JUMP_KEY
+        } else {
+            bindings.jump
+        };
+        if keyboard.just_pressed(jump) {
+            jump_state.buffer = Some(Timer::new(movement.jump_buffer, TimerMode::Once));
+        }
+        // Player two has no key to spare for dashing.
+        if !second && keyboard.just_pressed(bindings.dash) {
+            dash_state.requested = true;
+        }
+    }
+}
+
+fn main_menu(assets: &FontAssets) -> impl Bundle + use<> {
+    (
+        Menu {
+            show_state: GameState::Menu,
+        },
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(10.0),
+            ..default()
+        },
+        children![
+            (
+                Text::new("Fissile Material"),
+                TextFont {
+                    font: assets.u_atom.clone(),
+                    font_size: 100.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextShadow::default(),
+            ),
+            (
+                LifetimeAtomsText,
+                Text::default(),
+                TextFont {
+                    font: assets.u_atom.clone(),
+                    font_size: 28.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                TextShadow::default(),
+            ),
+            (
+                BestTimeText,
+                Text::default(),
+                TextFont {
+                    font: assets.u_atom.clone(),
+                    font_size: 28.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                TextShadow::default(),
+            ),
+            (
+                Button,
+                LevelMenuAction::Open,
+                Node {
+                    width: Val::Px(300.0),
+                    height: Val::Px(80.0),
+                    border: UiRect::all(Val::Px(5.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BorderColor(Color::BLACK),
+                BorderRadius::MAX,
+                BackgroundColor(NORMAL_BUTTON),
+                children![(
+                    Text::new("Start Game"),
+                    TextFont {
+                        font: assets.u_atom.clone(),
+                        font_size: 38.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    TextShadow::default(),
+                )]
+            ),
+            (
+                Button,
+                SettingsMenuAction::Open,
+                Node {
+                    width: Val::Px(300.0),
+                    height: Val::Px(80.0),
+                    border: UiRect::all(Val::Px(5.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BorderColor(Color::BLACK),
+                BorderRadius::MAX,
+                BackgroundColor(NORMAL_BUTTON),
+                children![(
+                    Text::new("Settings"),
+                    TextFont {
+                        font: assets.u_atom.clone(),
+                        font_size: 38.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    TextShadow::default(),
+                )]
+            ),
+            (
+                Button,
+                ControlsMenuAction::Open,
+                Node {
+                    width: Val::Px(300.0),
+                    height: Val::Px(80.0),
+                    border: UiRect::all(Val::Px(5.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BorderColor(Color::BLACK),
+                BorderRadius::MAX,
+                BackgroundColor(NORMAL_BUTTON),
+                children![(
+                    Text::new("Controls"),
+                    TextFont {
+                        font: assets.u_atom.clone(),
+                        font_size: 38.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    TextShadow::default(),
+                )]
+            ),
+            (
+                Button,
+                BenchmarkMenuAction::Open,
+                Node {
+                    width: Val::Px(300.0),
+                    height: Val::Px(80.0),
+                    border: UiRect::all(Val::Px(5.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BorderColor(Color::BLACK),
+                BorderRadius::MAX,
+                BackgroundColor(NORMAL_BUTTON),
+                children![(
+                    Text::new("Benchmark"),
+                    TextFont {
+                        font: assets.u_atom.clone(),
+                        font_size: 38.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    TextShadow::default(),
+                )]
+            ),
+            (
+                Button,
+                StatsMenuAction::Open,
+                Node {
+                    width: Val::Px(300.0),
+                    height: Val::Px(80.0),
+                    border: UiRect::all(Val::Px(5.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BorderColor(Color::BLACK),
+                BorderRadius::MAX,
+                BackgroundColor(NORMAL_BUTTON),
+                children![(
+                    Text::new("Statistics"),
+                    TextFont {
+                        font: assets.u_atom.clone(),
+                        font_size: 38.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    TextShadow::default(),
+                )]
+            ),
+            (
+                Button,
+                CreditsMenuAction::Open,
+                Node {
+                    width: Val::Px(300.0),
+                    height: Val::Px(80.0),
+                    border: UiRect::all(Val::Px(5.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BorderColor(Color::BLACK),
+                BorderRadius::MAX,
+                BackgroundColor(NORMAL_BUTTON),
+                children![(
+                    Text::new("Credits"),
+                    TextFont {
+                        font: assets.u_atom.clone(),
+                        font_size: 38.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    TextShadow::default(),
+                )]
+            ),
+            (
+                Button,
+                QuitButton,
+                Node {
+                    width: Val::Px(300.0),
+                    height: Val::Px(80.0),
+                    border: UiRect::all(Val::Px(5.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BorderColor(Color::BLACK),
+                BorderRadius::MAX,
+                BackgroundColor(NORMAL_BUTTON),
+                children![(
+                    Text::new("Quit"),
+                    TextFont {
+                        font: assets.u_atom.clone(),
+                        font_size: 38.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    TextShadow::default(),
+                )]
+            )
+        ],
+    )
+}
+
+/// Who made the game and what it's built on.
+const CREDITS: &str = "Built with Bevy\n\
+    avian3d, bevy-tnua, bevy_third_person_camera, bevy_seedling,\n\
+    bevy_asset_loader, iyes_progress, rand, serde and ron\n\
+    \n\
+    Sounds from freesound.org by bevibeldesign, unfa, lagomen and beman87\n\
+    Noto Serif by The Noto Project Authors";
 
-    if keyboard.pressed(KeyCode::Space) {
-        controller.action(TnuaBuiltinJump {
-            height: 2.5,
-            ..Default::default()
-        });
-    }
+fn credits_menu(assets: &FontAssets) -> impl Bundle + use<> {
+    (
+        Menu {
+            show_state: GameState::Credits,
+        },
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(10.0),
+            ..default()
+        },
+        children![
+            (
+                Text::new("Credits"),
+                TextFont {
+                    font: assets.u_atom.clone(),
+                    font_size: 100.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextShadow::default(),
+            ),
+            (
+                Text::new(CREDITS),
+                TextFont {
+                    font: assets.u_atom.clone(),
+                    font_size: 28.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                TextLayout::new_with_justify(JustifyText::Center),
+                TextShadow::default(),
+            ),
+            (
+                Button,
+                CreditsMenuAction::Back,
+                Node {
+                    width: Val::Px(300.0),
+                    height: Val::Px(80.0),
+                    border: UiRect::all(Val::Px(5.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BorderColor(Color::BLACK),
+                BorderRadius::MAX,
+                BackgroundColor(NORMAL_BUTTON),
+                children![(
+                    Text::new("Back"),
+                    TextFont {
+                        font: assets.u_atom.clone(),
+                        font_size: 38.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    TextShadow::default(),
+                )]
+            ),
+        ],
+    )
 }
 
-fn main_menu(assets: &FontAssets) -> impl Bundle + use<> {
+fn win_menu(assets: &FontAssets) -> impl Bundle + use<> {
     (
         Menu {
-            show_state: GameState::Menu,
+            show_state: GameState::Win,
         },
         Node {
             width: Val::Percent(100.0),
@@ -512,10 +2297,83 @@ fn main_menu(assets: &FontAssets) -> impl Bundle + use<> {
         },
         children![
             (
-                Text::new("Fissile Material"),
+                Text::new("Congratulations!"),
+                StartInvisible {
+                    time: Duration::from_secs(1)
+                },
                 TextFont {
                     font: assets.u_atom.clone(),
-                    font_size: 100.0,
+                    font_size: 110.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextShadow::default(),
+            ),
+            (
+                Text::new("200000 Civilian Deaths!"),
+                StartInvisible {
+                    time: Duration::from_secs(3)
+                },
+                DeathCountText,
+                TextFont {
+                    font: assets.u_atom.clone(),
+                    font_size: 62.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextShadow::default(),
+            ),
+            (
+                Text::default(),
+                StartInvisible {
+                    time: Duration::from_secs(4)
+                },
+                RunTimeText,
+                TextFont {
+                    font: assets.u_atom.clone(),
+                    font_size: 38.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextShadow::default(),
+            ),
+            (
+                Text::default(),
+                StartInvisible {
+                    time: Duration::from_secs(4)
+                },
+                NewBestText,
+                TextFont {
+                    font: assets.u_atom.clone(),
+                    font_size: 38.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextShadow::default(),
+            ),
+            (
+                Text::default(),
+                StartInvisible {
+                    time: Duration::from_secs(4)
+                },
+                EfficiencyText,
+                TextFont {
+                    font: assets.u_atom.clone(),
+                    font_size: 38.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextShadow::default(),
+            ),
+            (
+                Text::default(),
+                StartInvisible {
+                    time: Duration::from_secs(4)
+                },
+                SeedText,
+                TextFont {
+                    font: assets.u_atom.clone(),
+                    font_size: 38.0,
                     ..default()
                 },
                 TextColor(Color::srgb(0.9, 0.9, 0.9)),
@@ -524,6 +2382,9 @@ fn main_menu(assets: &FontAssets) -> impl Bundle + use<> {
             (
                 Button,
                 StartButton,
+                StartInvisible {
+                    time: Duration::from_secs(5)
+                },
                 Node {
                     width: Val::Px(300.0),
                     height: Val::Px(80.0),
@@ -536,7 +2397,35 @@ fn main_menu(assets: &FontAssets) -> impl Bundle + use<> {
                 BorderRadius::MAX,
                 BackgroundColor(NORMAL_BUTTON),
                 children![(
-                    Text::new("Start Game"),
+                    Text::new("Play Again"),
+                    TextFont {
+                        font: assets.u_atom.clone(),
+                        font_size: 38.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    TextShadow::default(),
+                )]
+            ),
+            (
+                Button,
+                NewLayoutButton,
+                StartInvisible {
+                    time: Duration::from_secs(5)
+                },
+                Node {
+                    width: Val::Px(300.0),
+                    height: Val::Px(80.0),
+                    border: UiRect::all(Val::Px(5.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BorderColor(Color::BLACK),
+                BorderRadius::MAX,
+                BackgroundColor(NORMAL_BUTTON),
+                children![(
+                    Text::new("New Layout"),
                     TextFont {
                         font: assets.u_atom.clone(),
                         font_size: 38.0,
@@ -546,9 +2435,18 @@ fn main_menu(assets: &FontAssets) -> impl Bundle + use<> {
                     TextShadow::default(),
                 )]
             ),
+            (
+                replay_button("Export Ghost", ReplayMenuAction::Export, assets),
+                StartInvisible {
+                    time: Duration::from_secs(5)
+                },
+            ),
             (
                 Button,
                 QuitButton,
+                StartInvisible {
+                    time: Duration::from_secs(5)
+                },
                 Node {
                     width: Val::Px(300.0),
                     height: Val::Px(80.0),
@@ -575,10 +2473,10 @@ fn main_menu(assets: &FontAssets) -> impl Bundle + use<> {
     )
 }
 
-fn win_menu(assets: &FontAssets) -> impl Bundle + use<> {
+fn lose_menu(assets: &FontAssets) -> impl Bundle + use<> {
     (
         Menu {
-            show_state: GameState::Win,
+            show_state: GameState::Lose,
         },
         Node {
             width: Val::Percent(100.0),
@@ -591,10 +2489,8 @@ fn win_menu(assets: &FontAssets) -> impl Bundle + use<> {
         },
         children![
             (
-                Text::new("Congratulations!"),
-                StartInvisible{
-                    time: Duration::from_secs(1)
-                },
+                Text::default(),
+                LoseTitleText,
                 TextFont {
                     font: assets.u_atom.clone(),
                     font_size: 110.0,
@@ -604,14 +2500,11 @@ fn win_menu(assets: &FontAssets) -> impl Bundle + use<> {
                 TextShadow::default(),
             ),
             (
-                Text::new("200000 Civilian Deaths!"),
-                StartInvisible{
-                    time: Duration::from_secs(3)
-                },
-                DeathCountText,
+                Text::default(),
+                LoseSummaryText,
                 TextFont {
                     font: assets.u_atom.clone(),
-                    font_size: 62.0,
+                    font_size: 38.0,
                     ..default()
                 },
                 TextColor(Color::srgb(0.9, 0.9, 0.9)),
@@ -620,9 +2513,6 @@ fn win_menu(assets: &FontAssets) -> impl Bundle + use<> {
             (
                 Button,
                 StartButton,
-                StartInvisible{
-                    time: Duration::from_secs(5)
-                },
                 Node {
                     width: Val::Px(300.0),
                     height: Val::Px(80.0),
@@ -635,7 +2525,7 @@ fn win_menu(assets: &FontAssets) -> impl Bundle + use<> {
                 BorderRadius::MAX,
                 BackgroundColor(NORMAL_BUTTON),
                 children![(
-                    Text::new("Play Again"),
+                    Text::new("Retry"),
                     TextFont {
                         font: assets.u_atom.clone(),
                         font_size: 38.0,
@@ -648,9 +2538,6 @@ fn win_menu(assets: &FontAssets) -> impl Bundle + use<> {
             (
                 Button,
                 QuitButton,
-                StartInvisible{
-                    time: Duration::from_secs(5)
-                },
                 Node {
                     width: Val::Px(300.0),
                     height: Val::Px(80.0),
@@ -682,6 +2569,7 @@ fn pause_menu(assets: &FontAssets) -> impl Bundle + use<> {
         Menu {
             show_state: GameState::Pause,
         },
+        PauseMenu,
         Node {
             width: Val::Percent(100.0),
             height: Val::Percent(100.0),
@@ -691,6 +2579,7 @@ fn pause_menu(assets: &FontAssets) -> impl Bundle + use<> {
             row_gap: Val::Px(10.0),
             ..default()
         },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
         children![
             (
                 Button,
@@ -717,6 +2606,31 @@ fn pause_menu(assets: &FontAssets) -> impl Bundle + use<> {
                     TextShadow::default(),
                 )]
             ),
+            (
+                Button,
+                SettingsMenuAction::Open,
+                Node {
+                    width: Val::Px(300.0),
+                    height: Val::Px(80.0),
+                    border: UiRect::all(Val::Px(5.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BorderColor(Color::BLACK),
+                BorderRadius::MAX,
+                BackgroundColor(NORMAL_BUTTON),
+                children![(
+                    Text::new("Settings"),
+                    TextFont {
+                        font: assets.u_atom.clone(),
+                        font_size: 38.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    TextShadow::default(),
+                )]
+            ),
             (
                 Button,
                 QuitButton,
@@ -745,3 +2659,87 @@ fn pause_menu(assets: &FontAssets) -> impl Bundle + use<> {
         ],
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::state::app::StatesPlugin;
+
+    use super::*;
+
+    #[test]
+    fn pause_restores_both_players_exactly() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .add_event::<MouseMotion>()
+            .insert_state(GameState::Game)
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Game,
+                    entered: GameState::Pause,
+                },
+                take_pause_snapshot,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Pause,
+                    entered: GameState::Game,
+                },
+                restore_pause_snapshot,
+            );
+
+        let moving = |x: f32| {
+            (
+                Transform::from_xyz(x, 2.0, -3.0).with_rotation(Quat::from_rotation_z(0.4)),
+                LinearVelocity(Vec3::new(12.5, -0.25, 3.0)),
+                AngularVelocity(Vec3::new(0.0, 1.5, -7.0)),
+            )
+        };
+        let player = app.world_mut().spawn((moving(1.0), Player)).id();
+        let second_player = app.world_mut().spawn((moving(4.0), SecondPlayer)).id();
+        let camera = app
+            .world_mut()
+            .spawn((
+                ThirdPersonCamera::default(),
+                Transform::from_xyz(0.0, 5.0, 10.0),
+            ))
+            .id();
+        app.update();
+
+        let state = |app: &App| {
+            [player, second_player, camera].map(|entity| {
+                let entity = app.world().entity(entity);
+                (
+                    *entity.get::<Transform>().unwrap(),
+                    entity.get::<LinearVelocity>().copied(),
+                    entity.get::<AngularVelocity>().copied(),
+                )
+            })
+        };
+        let before = state(&app);
+
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Pause);
+        app.update();
+
+        // Anything that nudges them while paused, like a solver step or the
+        // camera plugin, is undone on resume.
+        for entity in [player, second_player] {
+            let mut entity = app.world_mut().entity_mut(entity);
+            entity.get_mut::<Transform>().unwrap().translation.y -= 0.5;
+            entity.get_mut::<LinearVelocity>().unwrap().0 *= 0.9;
+            entity.get_mut::<AngularVelocity>().unwrap().0 = Vec3::ZERO;
+        }
+        app.world_mut()
+            .get_mut::<Transform>(camera)
+            .unwrap()
+            .rotate_y(1.0);
+
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Game);
+        app.update();
+
+        assert_eq!(state(&app), before);
+    }
+}
@@ -0,0 +1,102 @@
+use bevy::{color::palettes::css, prelude::*, render::view::RenderLayers};
+
+use crate::{AtomAssets, GameState, MenuCamera};
+
+/// Far above the level, so nothing left over from a run wanders into view.
+const ORIGIN: Vec3 = Vec3::new(0.0, 1000.0, 0.0);
+/// Radians per second.
+const SPIN_SPEED: f32 = 0.3;
+
+/// A single slowly turning atom behind the main menu. It has its own 3D
+/// camera drawing under the menu camera, which stops clearing the screen
+/// while it's up. One model and one unshadowed light keep it cheap.
+pub struct MenuBackgroundPlugin;
+
+impl Plugin for MenuBackgroundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                setup_menu_background.run_if(
+                    in_state(GameState::Menu).and(not(any_with_component::<MenuBackground>)),
+                ),
+                spin_atom.run_if(in_state(GameState::Menu)),
+                menu_camera_clear,
+            ),
+        )
+        .add_systems(OnExit(GameState::Menu), teardown_menu_background);
+    }
+}
+
+/// Everything spawned for the background.
+#[derive(Component)]
+struct MenuBackground;
+
+#[derive(Component)]
+struct SpinningAtom;
+
+fn setup_menu_background(mut commands: Commands, atom_assets: Res<AtomAssets>) {
+    commands.spawn((
+        MenuBackground,
+        Camera3d::default(),
+        Camera {
+            // Under the menu camera.
+            order: -1,
+            clear_color: ClearColorConfig::Custom(css::DARK_GRAY.into()),
+            ..default()
+        },
+        RenderLayers::layer(0),
+        Transform::from_translation(ORIGIN + Vec3::new(0.0, 3.0, 16.0)).looking_at(ORIGIN, Vec3::Y),
+    ));
+
+    commands.spawn((
+        MenuBackground,
+        SpinningAtom,
+        SceneRoot(atom_assets.u_atom.clone()),
+        Transform::from_translation(ORIGIN),
+    ));
+
+    commands.spawn((
+        MenuBackground,
+        PointLight {
+            intensity: 2_000_000.0,
+            range: 40.0,
+            shadows_enabled: false,
+            ..default()
+        },
+        Transform::from_translation(ORIGIN + Vec3::new(6.0, 8.0, 10.0)),
+    ));
+}
+
+fn spin_atom(time: Res<Time>, mut atoms: Query<&mut Transform, With<SpinningAtom>>) {
+    for mut transform in atoms.iter_mut() {
+        transform.rotate_y(SPIN_SPEED * time.delta_secs());
+    }
+}
+
+/// The menu camera would paint over the background, so it only clears the
+/// screen itself while there's no background under it.
+fn menu_camera_clear(
+    background: Query<(), With<MenuBackground>>,
+    mut menu_camera: Query<&mut Camera, With<MenuCamera>>,
+) {
+    let clear_color = if background.is_empty() {
+        ClearColorConfig::Default
+    } else {
+        ClearColorConfig::None
+    };
+    for mut camera in menu_camera.iter_mut() {
+        if std::mem::discriminant(&camera.clear_color) != std::mem::discriminant(&clear_color) {
+            camera.clear_color = clear_color;
+        }
+    }
+}
+
+fn teardown_menu_background(
+    mut commands: Commands,
+    background: Query<Entity, With<MenuBackground>>,
+) {
+    for entity in background.iter() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,157 @@
+use bevy::prelude::*;
+
+use crate::controls::awaiting_key;
+use crate::theme::ColorTheme;
+use crate::{GameState, Menu, button_visuals};
+
+/// Keyboard and gamepad navigation for the menus. Up/Down (or the D-pad) move
+/// a focus through the shown menu's buttons and Enter (or South) presses the
+/// focused one, the same as a click would.
+pub struct MenuNavPlugin;
+
+impl Plugin for MenuNavPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MenuFocus>().add_systems(
+            Update,
+            (
+                (
+                    reset_focus.run_if(state_changed::<GameState>.or(modal_toggled)),
+                    navigate.run_if(not(in_state(GameState::Game)).and(not(awaiting_key))),
+                )
+                    .chain()
+                    .before(button_visuals),
+                focus_visuals.after(button_visuals),
+            ),
+        );
+    }
+}
+
+/// A panel shown over a menu, such as a confirmation. While one is visible
+/// it takes the focus from the menu beneath it.
+#[derive(Component)]
+pub struct Modal;
+
+#[derive(Resource, Default)]
+struct MenuFocus {
+    /// Position in the shown menu's buttons, in layout order. Nothing is
+    /// focused until the player first navigates or hovers a button.
+    index: Option<usize>,
+    entity: Option<Entity>,
+    /// A button pressed from the keyboard or gamepad, let go of next frame
+    /// since no mouse release will come for it.
+    pressed: Option<Entity>,
+}
+
+fn modal_toggled(modals: Query<(), (With<Modal>, Changed<Visibility>)>) -> bool {
+    !modals.is_empty()
+}
+
+fn reset_focus(mut focus: ResMut<MenuFocus>) {
+    focus.index = None;
+    focus.entity = None;
+}
+
+fn navigate(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    state: Res<State<GameState>>,
+    menus: Query<(Entity, &Menu)>,
+    modals: Query<(Entity, &Visibility), With<Modal>>,
+    children: Query<&Children>,
+    mut buttons: Query<(&mut Interaction, &InheritedVisibility), With<Button>>,
+    mut focus: ResMut<MenuFocus>,
+) {
+    if let Some(entity) = focus.pressed.take()
+        && let Ok((mut interaction, _)) = buttons.get_mut(entity)
+        && *interaction == Interaction::Pressed
+    {
+        *interaction = Interaction::None;
+    }
+
+    let roots: Vec<Entity> = match modals
+        .iter()
+        .find(|(_, visibility)| **visibility != Visibility::Hidden)
+    {
+        Some((modal, _)) => vec![modal],
+        None => menus
+            .iter()
+            .filter(|(_, menu)| menu.show_state == *state.get())
+            .map(|(menu, _)| menu)
+            .collect(),
+    };
+    let focusable: Vec<Entity> = roots
+        .into_iter()
+        .flat_map(|root| children.iter_descendants_depth_first(root))
+        .filter(|entity| {
+            buttons
+                .get(*entity)
+                .is_ok_and(|(_, visibility)| visibility.get())
+        })
+        .collect();
+    if focusable.is_empty() {
+        return;
+    }
+    let len = focusable.len();
+
+    // Follow the mouse, so there's only ever one highlighted button.
+    if let Some(hovered) = focusable.iter().position(|entity| {
+        buttons.get_mut(*entity).is_ok_and(|(interaction, _)| {
+            interaction.is_changed() && *interaction == Interaction::Hovered
+        })
+    }) {
+        focus.index = Some(hovered);
+    }
+
+    let pressed = |key: KeyCode, button: GamepadButton| {
+        keyboard.just_pressed(key) || gamepads.iter().any(|pad| pad.just_pressed(button))
+    };
+    if pressed(KeyCode::ArrowUp, GamepadButton::DPadUp) {
+        focus.index = Some(focus.index.map_or(len - 1, |i| (i + len - 1) % len));
+    }
+    if pressed(KeyCode::ArrowDown, GamepadButton::DPadDown) {
+        focus.index = Some(focus.index.map_or(0, |i| (i + 1) % len));
+    }
+    // Buttons can disappear while focused, e.g. on the win screen.
+    focus.index = focus.index.map(|i| i.min(len - 1));
+    focus.entity = focus.index.map(|i| focusable[i]);
+
+    if pressed(KeyCode::Enter, GamepadButton::South)
+        && let Some(entity) = focus.entity
+        && let Ok((mut interaction, _)) = buttons.get_mut(entity)
+    {
+        *interaction = Interaction::Pressed;
+        focus.pressed = Some(entity);
+    }
+}
+
+/// Draws the focused button as hovered. Runs after `button_visuals` so a
+/// released press doesn't leave it looking unfocused.
+fn focus_visuals(
+    focus: Res<MenuFocus>,
+    theme: Res<ColorTheme>,
+    mut highlighted: Local<Option<Entity>>,
+    mut buttons: Query<(&Interaction, &mut BackgroundColor, &mut BorderColor), With<Button>>,
+) {
+    if *highlighted != focus.entity {
+        if let Some(entity) = highlighted.take()
+            && let Ok((interaction, mut color, mut border_color)) = buttons.get_mut(entity)
+            && *interaction == Interaction::None
+        {
+            let (background, border) = theme.button(Interaction::None);
+            *color = background.into();
+            border_color.0 = border;
+        }
+        *highlighted = focus.entity;
+    }
+
+    let Some(entity) = focus.entity else {
+        return;
+    };
+    if let Ok((interaction, mut color, mut border_color)) = buttons.get_mut(entity)
+        && *interaction == Interaction::None
+    {
+        let (background, border) = theme.button(Interaction::Hovered);
+        color.set_if_neq(background.into());
+        border_color.set_if_neq(BorderColor(border));
+    }
+}
@@ -0,0 +1,171 @@
+use bevy::prelude::*;
+use bevy_third_person_camera::ThirdPersonCamera;
+
+use crate::ghost::GhostConfig;
+use crate::settings::Settings;
+use crate::theme::ColorTheme;
+use crate::{GameState, Menu, Player, WinGame};
+
+/// Side length of an atom's dot, in pixels. The player's is a little larger.
+const DOT_SIZE: f32 = 6.0;
+
+/// A top-down map in the corner of the screen, centred on the player with
+/// north up, showing the atoms still to be collected. With ghost atoms on,
+/// only those already revealed are shown. It's plain UI drawn
+/// through the game camera like the HUD, so it needs no camera of its own and
+/// hides with the rest of the HUD outside `GameState::Game`.
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MinimapConfig>().add_systems(
+            Update,
+            (
+                setup_minimap.run_if(not(any_with_component::<Minimap>)),
+                (sync_atom_dots, place_dots).chain(),
+            )
+                .chain()
+                .run_if(in_state(GameState::Game)),
+        );
+    }
+}
+
+#[derive(Resource)]
+pub struct MinimapConfig {
+    /// Side length of the map, in pixels.
+    pub size: f32,
+    /// World distance from the player to the edge of the map.
+    pub range: f32,
+}
+
+impl Default for MinimapConfig {
+    fn default() -> Self {
+        Self {
+            size: 180.0,
+            range: 100.0,
+        }
+    }
+}
+
+#[derive(Component)]
+struct Minimap;
+
+#[derive(Component)]
+struct PlayerDot;
+
+/// The dot standing in for an atom.
+#[derive(Component)]
+struct AtomDot(Entity);
+
+/// Marks an atom that already has a dot.
+#[derive(Component)]
+struct OnMinimap;
+
+fn setup_minimap(
+    mut commands: Commands,
+    config: Res<MinimapConfig>,
+    theme: Res<ColorTheme>,
+    camera: Single<Entity, With<ThirdPersonCamera>>,
+) {
+    let player_dot = DOT_SIZE * 1.5;
+    commands.spawn((
+        Minimap,
+        Menu {
+            show_state: GameState::Game,
+        },
+        UiTargetCamera(*camera),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(20.0),
+            right: Val::Px(20.0),
+            width: Val::Px(config.size),
+            height: Val::Px(config.size),
+            border: UiRect::all(Val::Px(2.0)),
+            overflow: Overflow::clip(),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+        BorderColor(Color::srgb(0.9, 0.9, 0.9)),
+        children![(
+            PlayerDot,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px((config.size - player_dot) / 2.0),
+                top: Val::Px((config.size - player_dot) / 2.0),
+                width: Val::Px(player_dot),
+                height: Val::Px(player_dot),
+                ..default()
+            },
+            BackgroundColor(theme.player),
+            BorderRadius::MAX,
+            // Over the atoms it's rolling into.
+            ZIndex(1),
+        )],
+    ));
+}
+
+/// Adds a dot for each new atom and drops those whose atom is gone.
+fn sync_atom_dots(
+    mut commands: Commands,
+    minimap: Single<Entity, With<Minimap>>,
+    theme: Res<ColorTheme>,
+    atoms: Query<Entity, (With<WinGame>, Without<OnMinimap>)>,
+    dots: Query<(Entity, &AtomDot)>,
+    existing: Query<(), With<WinGame>>,
+) {
+    for (dot, atom) in dots.iter() {
+        if !existing.contains(atom.0) {
+            commands.entity(dot).despawn();
+        }
+    }
+
+    for atom in atoms.iter() {
+        commands.entity(atom).insert(OnMinimap);
+        commands.entity(*minimap).with_child((
+            AtomDot(atom),
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Px(DOT_SIZE),
+                height: Val::Px(DOT_SIZE),
+                ..default()
+            },
+            BackgroundColor(theme.highlight),
+            BorderRadius::MAX,
+            Visibility::Hidden,
+        ));
+    }
+}
+
+fn place_dots(
+    config: Res<MinimapConfig>,
+    ghost: Res<GhostConfig>,
+    settings: Res<Settings>,
+    player: Query<&GlobalTransform, With<Player>>,
+    atoms: Query<&GlobalTransform, With<WinGame>>,
+    mut dots: Query<(&AtomDot, &mut Node, &mut Visibility)>,
+) {
+    let Ok(player) = player.single() else {
+        return;
+    };
+    let center = player.translation();
+    let scale = config.size / 2.0 / config.range.max(f32::EPSILON);
+
+    for (dot, mut node, mut visibility) in dots.iter_mut() {
+        let Ok(atom) = atoms.get(dot.0) else {
+            continue;
+        };
+        let offset = (atom.translation() - center).xz();
+        if offset.abs().max_element() > config.range
+            || ghost.hides(&settings, atom.translation().distance(center))
+        {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        }
+
+        // World +x is east (right) and +z is south (down).
+        let position = Vec2::splat(config.size / 2.0) + offset * scale - DOT_SIZE / 2.0;
+        node.left = Val::Px(position.x);
+        node.top = Val::Px(position.y);
+        visibility.set_if_neq(Visibility::Inherited);
+    }
+}
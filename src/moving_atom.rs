@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+
+use crate::GameState;
+
+/// Sways some atoms back and forth along a line so they can't all be swept
+/// up on one straight run.
+pub struct MovingAtomPlugin;
+
+impl Plugin for MovingAtomPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, move_atoms.run_if(in_state(GameState::Game)));
+    }
+}
+
+/// An atom oscillating about where it was spawned. Moving atoms are
+/// kinematic rather than static, so physics and the player's ground sensor
+/// follow them.
+#[derive(Component, Clone)]
+pub struct MovingAtom {
+    /// Direction of travel.
+    pub axis: Vec3,
+    /// Speed through the middle of the swing.
+    pub speed: f32,
+    /// Distance from the middle to either end.
+    pub range: f32,
+    origin: Vec3,
+    /// Position in the cycle, in radians.
+    phase: f32,
+}
+
+impl MovingAtom {
+    pub fn new(origin: Vec3, axis: Vec3, speed: f32, range: f32, phase: f32) -> Self {
+        Self {
+            axis: axis.normalize_or_zero(),
+            speed,
+            range,
+            origin,
+            phase,
+        }
+    }
+}
+
+fn move_atoms(time: Res<Time>, mut atoms: Query<(&mut MovingAtom, &mut Transform)>) {
+    for (mut atom, mut transform) in atoms.iter_mut() {
+        if atom.range <= 0.0 {
+            continue;
+        }
+        // Sinusoidal, so the peak speed is `speed` when the rate is
+        // speed / range.
+        atom.phase = (atom.phase + atom.speed / atom.range * time.delta_secs())
+            .rem_euclid(std::f32::consts::TAU);
+        transform.translation = atom.origin + atom.axis * atom.range * atom.phase.sin();
+    }
+}
@@ -0,0 +1,144 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use crate::countdown::RunStarted;
+use crate::hud::{AtomCountText, ObjectiveText};
+use crate::{GameState, LevelEntity};
+
+pub const DETONATOR_POSITION: Vec3 = Vec3::new(-30.0, 1.0, 0.0);
+
+pub struct ObjectivePlugin;
+
+impl Plugin for ObjectivePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Objective>()
+            .init_resource::<Collected>()
+            .add_systems(
+                Update,
+                (
+                    reset_collected.run_if(on_event::<RunStarted>),
+                    objective_text,
+                    atom_count_text,
+                    sync_detonator.run_if(resource_changed::<Objective>),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Game)),
+            );
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default)]
+pub enum ObjectivePhase {
+    /// Collecting atoms until critical mass is reached.
+    #[default]
+    Charging,
+    /// Critical mass reached, the detonator is waiting to be touched.
+    Armed,
+}
+
+#[derive(Resource, Default)]
+pub struct Objective {
+    pub phase: ObjectivePhase,
+    pub collected: u32,
+    pub required: u32,
+    /// Every atom the level started with.
+    pub total: u32,
+}
+
+impl Objective {
+    /// `critical_mass` is the fraction of the atoms that must be collected
+    /// before the detonator is armed.
+    pub fn new(atom_count: u32, critical_mass: f32) -> Self {
+        Self {
+            required: ((atom_count as f32 * critical_mass).ceil() as u32).max(1),
+            total: atom_count,
+            ..default()
+        }
+    }
+
+    /// Records a collected atom, arming the detonator once critical mass is reached.
+    pub fn collect(&mut self) {
+        self.collected += 1;
+        if self.collected >= self.required {
+            self.phase = ObjectivePhase::Armed;
+        }
+    }
+
+    /// Undoes a `collect`, disarming the detonator if that drops below
+    /// critical mass.
+    pub fn uncollect(&mut self) {
+        self.collected = self.collected.saturating_sub(1);
+        if self.collected < self.required {
+            self.phase = ObjectivePhase::Charging;
+        }
+    }
+}
+
+/// Atoms collected so far this run, shown against the level's total.
+#[derive(Resource, Default)]
+pub struct Collected(pub u32);
+
+#[derive(Component)]
+pub struct Detonator;
+
+fn reset_collected(mut collected: ResMut<Collected>) {
+    collected.0 = 0;
+}
+
+fn objective_text(
+    objective: Res<Objective>,
+    mut text_query: Query<&mut Text, With<ObjectiveText>>,
+) {
+    for mut text in text_query.iter_mut() {
+        text.0 = match objective.phase {
+            ObjectivePhase::Charging => format!(
+                "Reach critical mass: {} / {} atoms",
+                objective.collected, objective.required
+            ),
+            ObjectivePhase::Armed => "Critical mass reached! Roll onto the detonator".to_string(),
+        };
+    }
+}
+
+fn atom_count_text(
+    collected: Res<Collected>,
+    objective: Res<Objective>,
+    mut text_query: Query<&mut Text, With<AtomCountText>>,
+) {
+    for mut text in text_query.iter_mut() {
+        text.0 = format!("{} / {} atoms", collected.0, objective.total);
+    }
+}
+
+fn sync_detonator(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    objective: Res<Objective>,
+    detonators: Query<Entity, With<Detonator>>,
+) {
+    if objective.phase != ObjectivePhase::Armed {
+        // A rewind can take the objective back below critical mass.
+        for detonator in detonators.iter() {
+            commands.entity(detonator).despawn();
+        }
+        return;
+    }
+    if !detonators.is_empty() {
+        return;
+    }
+
+    commands.spawn((
+        Mesh3d(meshes.add(Cylinder::new(3.0, 2.0))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::srgb(0.8, 0.1, 0.1),
+            emissive: LinearRgba::rgb(2.0, 0.1, 0.1),
+            ..default()
+        })),
+        Transform::from_translation(DETONATOR_POSITION),
+        RigidBody::Static,
+        Collider::cylinder(3.0, 2.0),
+        Detonator,
+        LevelEntity,
+    ));
+}
@@ -0,0 +1,82 @@
+use std::f32::consts::FRAC_PI_2;
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_tnua::TnuaProximitySensor;
+use bevy_tnua_avian3d::TnuaAvian3dSensorShape;
+
+pub const TOGGLE_KEY: KeyCode = KeyCode::F4;
+
+/// Draws colliders with Avian's debug renderer, toggled with F4. Off by
+/// default. Tnua's ground sensor is cast through spatial queries rather
+/// than a collider, so it's drawn here alongside them.
+pub struct PhysicsDebugTogglePlugin;
+
+impl Plugin for PhysicsDebugTogglePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PhysicsDebug>().add_systems(
+            Update,
+            (
+                toggle_physics_debug,
+                apply_physics_debug.run_if(resource_changed::<PhysicsDebug>),
+                draw_ground_sensor.run_if(physics_debug_visible),
+            )
+                .chain(),
+        );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct PhysicsDebug {
+    pub visible: bool,
+}
+
+fn physics_debug_visible(debug: Res<PhysicsDebug>) -> bool {
+    debug.visible
+}
+
+fn toggle_physics_debug(keyboard: Res<ButtonInput<KeyCode>>, mut debug: ResMut<PhysicsDebug>) {
+    if keyboard.just_pressed(TOGGLE_KEY) {
+        debug.visible = !debug.visible;
+    }
+}
+
+fn apply_physics_debug(debug: Res<PhysicsDebug>, mut store: ResMut<GizmoConfigStore>) {
+    store.config_mut::<PhysicsGizmos>().0.enabled = debug.visible;
+}
+
+/// The sensor's shape at the start and end of its cast, joined by a line.
+fn draw_ground_sensor(
+    mut gizmos: Gizmos<PhysicsGizmos>,
+    sensors: Query<(
+        &GlobalTransform,
+        &TnuaProximitySensor,
+        Option<&TnuaAvian3dSensorShape>,
+    )>,
+) {
+    for (transform, sensor, shape) in sensors.iter() {
+        let radius = shape
+            .and_then(|shape| {
+                shape
+                    .0
+                    .shape()
+                    .as_cylinder()
+                    .map(|cylinder| cylinder.radius)
+            })
+            .unwrap_or(0.0);
+        let start = transform.transform_point(sensor.cast_origin);
+        let end = start + *sensor.cast_direction * sensor.cast_range;
+        let color = if sensor.output.is_some() {
+            Color::srgb(0.2, 0.9, 0.3)
+        } else {
+            Color::srgb(0.9, 0.3, 0.2)
+        };
+
+        gizmos.line(start, end, color);
+        if radius > 0.0 {
+            let flat = Quat::from_rotation_x(FRAC_PI_2);
+            gizmos.circle(Isometry3d::new(start, flat), radius, color);
+            gizmos.circle(Isometry3d::new(end, flat), radius, color);
+        }
+    }
+}
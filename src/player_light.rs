@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+
+use crate::settings::Settings;
+
+/// A point light carried by the ball, so it lights up the atoms and ground
+/// around it. Toggled by `Settings::player_light`.
+pub struct PlayerLightPlugin;
+
+impl Plugin for PlayerLightPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PlayerLightConfig>().add_systems(
+            Update,
+            toggle_player_light.run_if(resource_changed::<Settings>),
+        );
+    }
+}
+
+#[derive(Resource)]
+pub struct PlayerLightConfig {
+    /// Luminous power in lumens. Kept well under the scene's own point light
+    /// so the glow doesn't wash out nearby atoms.
+    pub intensity: f32,
+    pub range: f32,
+}
+
+impl Default for PlayerLightConfig {
+    fn default() -> Self {
+        Self {
+            intensity: 150_000.0,
+            range: 20.0,
+        }
+    }
+}
+
+#[derive(Component)]
+struct PlayerLight;
+
+/// The light itself, spawned as a child of the player in the ball's colour.
+/// Starts out as the settings have it, since they may not change again.
+pub fn player_light(
+    config: &PlayerLightConfig,
+    settings: &Settings,
+    color: Color,
+) -> impl Bundle + use<> {
+    (
+        PlayerLight,
+        PointLight {
+            color,
+            intensity: config.intensity,
+            range: config.range,
+            // A shadow-casting point light renders six extra views per frame.
+            shadows_enabled: false,
+            ..default()
+        },
+        Transform::default(),
+        light_visibility(settings),
+    )
+}
+
+fn light_visibility(settings: &Settings) -> Visibility {
+    if settings.player_light {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+fn toggle_player_light(
+    settings: Res<Settings>,
+    mut lights: Query<&mut Visibility, With<PlayerLight>>,
+) {
+    let visibility = light_visibility(&settings);
+    for mut light in lights.iter_mut() {
+        light.set_if_neq(visibility);
+    }
+}
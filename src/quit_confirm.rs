@@ -0,0 +1,168 @@
+use bevy::prelude::*;
+use bevy::render::view::RenderLayers;
+use bevy::ui::FocusPolicy;
+use bevy_third_person_camera::ThirdPersonCamera;
+
+use crate::menu_nav::Modal;
+use crate::{FontAssets, GameState, NORMAL_BUTTON};
+
+/// An "Are you sure?" panel shown over the current menu when Quit is pressed.
+/// Only Yes actually exits.
+pub struct QuitConfirmPlugin;
+
+impl Plugin for QuitConfirmPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<QuitConfirm>().add_systems(
+            Update,
+            (
+                setup_quit_confirm.run_if(in_state(GameState::Menu).and(run_once)),
+                close_quit_confirm.run_if(state_changed::<GameState>),
+                quit_confirm_button_system,
+                show_quit_confirm.run_if(resource_changed::<QuitConfirm>),
+            )
+                .chain(),
+        );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct QuitConfirm {
+    pub open: bool,
+}
+
+#[derive(Component)]
+struct QuitConfirmPanel;
+
+#[derive(Component, Clone, Copy)]
+enum QuitConfirmAction {
+    Yes,
+    No,
+}
+
+fn setup_quit_confirm(mut commands: Commands, font_assets: Res<FontAssets>) {
+    commands.spawn((
+        QuitConfirmPanel,
+        Modal,
+        RenderLayers::layer(1),
+        Visibility::Hidden,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(20.0),
+            ..default()
+        },
+        // Keeps clicks off the menu underneath.
+        FocusPolicy::Block,
+        GlobalZIndex(10),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        children![
+            (
+                Text::new("Are you sure?"),
+                TextFont {
+                    font: font_assets.u_atom.clone(),
+                    font_size: 60.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextShadow::default(),
+            ),
+            (
+                Node {
+                    column_gap: Val::Px(20.0),
+                    ..default()
+                },
+                children![
+                    quit_confirm_button("Yes", QuitConfirmAction::Yes, &font_assets),
+                    quit_confirm_button("No", QuitConfirmAction::No, &font_assets),
+                ],
+            ),
+        ],
+    ));
+}
+
+fn quit_confirm_button(
+    label: &str,
+    action: QuitConfirmAction,
+    assets: &FontAssets,
+) -> impl Bundle + use<> {
+    (
+        Button,
+        action,
+        Node {
+            width: Val::Px(160.0),
+            height: Val::Px(80.0),
+            border: UiRect::all(Val::Px(5.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BorderColor(Color::BLACK),
+        BorderRadius::MAX,
+        BackgroundColor(NORMAL_BUTTON),
+        children![(
+            Text::new(label),
+            TextFont {
+                font: assets.u_atom.clone(),
+                font_size: 38.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+            TextShadow::default(),
+        )],
+    )
+}
+
+fn close_quit_confirm(mut quit_confirm: ResMut<QuitConfirm>) {
+    quit_confirm.open = false;
+}
+
+fn quit_confirm_button_system(
+    interaction_query: Query<
+        (&Interaction, &QuitConfirmAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut quit_confirm: ResMut<QuitConfirm>,
+    mut exit: EventWriter<AppExit>,
+) {
+    for (interaction, action) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match *action {
+            QuitConfirmAction::Yes => {
+                exit.write(AppExit::Success);
+            }
+            QuitConfirmAction::No => quit_confirm.open = false,
+        }
+    }
+}
+
+/// The pause menu draws through the game camera, so the panel follows it
+/// there.
+fn show_quit_confirm(
+    mut commands: Commands,
+    quit_confirm: Res<QuitConfirm>,
+    state: Res<State<GameState>>,
+    game_camera: Query<Entity, With<ThirdPersonCamera>>,
+    mut panel: Single<(Entity, &mut Visibility), With<QuitConfirmPanel>>,
+) {
+    let (entity, visibility) = &mut *panel;
+    if !quit_confirm.open {
+        **visibility = Visibility::Hidden;
+        return;
+    }
+
+    **visibility = Visibility::Visible;
+    match game_camera.single() {
+        Ok(camera) if *state.get() == GameState::Pause => {
+            commands.entity(*entity).insert(UiTargetCamera(camera));
+        }
+        _ => {
+            commands.entity(*entity).remove::<UiTargetCamera>();
+        }
+    }
+}
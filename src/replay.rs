@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+
+use bevy::{color::palettes::css, prelude::*};
+use bevy_third_person_camera::ThirdPersonCameraTarget;
+use serde::{Deserialize, Serialize};
+
+use crate::countdown::{RunStarted, RunTimer};
+use crate::levels::{LevelConfig, Levels, SelectedLevel};
+use crate::{FontAssets, GameState, NORMAL_BUTTON, storage};
+
+const GHOST_PATH: &str = "ghost.ron";
+const BEST_RUNS_PATH: &str = "best_runs.ron";
+/// Bumped whenever `GhostRun` changes shape, so old files are rejected
+/// instead of misread.
+const GHOST_VERSION: u32 = 2;
+const SAMPLE_INTERVAL: f32 = 0.05;
+
+/// Records the player's path during each run. A finished run can be exported
+/// from the win screen, and an exported run loaded from the level select
+/// screen to race against as a translucent ghost. Without one loaded, the
+/// ghost replays the fastest run so far on the level's current layout, which
+/// is kept from one session to the next.
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        let mut best_runs = storage::load::<BestRuns>(BEST_RUNS_PATH);
+        best_runs.runs.retain(|_, run| run.version == GHOST_VERSION);
+
+        app.init_resource::<Recording>()
+            .init_resource::<LoadedGhost>()
+            .init_resource::<ActiveGhost>()
+            .insert_resource(best_runs)
+            .add_systems(FixedUpdate, record_run.run_if(in_state(GameState::Game)))
+            .add_systems(
+                Update,
+                (
+                    start_run.run_if(on_event::<RunStarted>),
+                    play_ghost.run_if(in_state(GameState::Game)),
+                    replay_button_system
+                        .run_if(in_state(GameState::LevelSelect).or(in_state(GameState::Win))),
+                ),
+            )
+            .add_systems(OnEnter(GameState::Win), finish_recording)
+            .add_systems(OnEnter(GameState::Menu), despawn_ghost)
+            .add_systems(Last, save_best_runs.run_if(resource_changed::<BestRuns>));
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GhostRun {
+    pub version: u32,
+    /// Name of the level the run was recorded on.
+    pub level: String,
+    /// `LevelConfig::seed` the level was laid out with.
+    pub seed: u64,
+    /// Completion time in seconds.
+    pub time: f32,
+    /// Player positions, as (seconds since "Go", position).
+    pub samples: Vec<(f32, Vec3)>,
+}
+
+impl GhostRun {
+    fn new(level: String, seed: u64) -> Self {
+        Self {
+            version: GHOST_VERSION,
+            level,
+            seed,
+            time: 0.0,
+            samples: Vec::new(),
+        }
+    }
+
+    fn position_at(&self, time: f32) -> Option<Vec3> {
+        let next = self.samples.partition_point(|(t, _)| *t <= time);
+        match (
+            self.samples.get(next.wrapping_sub(1)),
+            self.samples.get(next),
+        ) {
+            (Some(&(t0, from)), Some(&(t1, to))) => {
+                Some(from.lerp(to, (time - t0) / (t1 - t0).max(f32::EPSILON)))
+            }
+            (Some(&(_, position)), None) | (None, Some(&(_, position))) => Some(position),
+            (None, None) => None,
+        }
+    }
+}
+
+/// The run in progress, and the last one that reached the win screen.
+#[derive(Resource, Default)]
+struct Recording {
+    current: Option<GhostRun>,
+    completed: Option<GhostRun>,
+}
+
+#[derive(Resource, Default)]
+struct LoadedGhost(Option<GhostRun>);
+
+/// The fastest finished run on each layout, by level name and seed. Runs on
+/// different layouts of the same level aren't comparable.
+#[derive(Resource, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct BestRuns {
+    runs: HashMap<(String, u64), GhostRun>,
+}
+
+/// The run the ghost is replaying, if any.
+#[derive(Resource, Default)]
+struct ActiveGhost(Option<GhostRun>);
+
+#[derive(Component)]
+struct ReplayGhost;
+
+#[derive(Component, Clone, Copy)]
+pub enum ReplayMenuAction {
+    Export,
+    Import,
+}
+
+fn start_run(
+    mut commands: Commands,
+    mut recording: ResMut<Recording>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    loaded: Res<LoadedGhost>,
+    best_runs: Res<BestRuns>,
+    mut active: ResMut<ActiveGhost>,
+    levels: Res<Levels>,
+    selected: Res<SelectedLevel>,
+    level_config: Res<LevelConfig>,
+    ghosts: Query<Entity, With<ReplayGhost>>,
+) {
+    for ghost in ghosts.iter() {
+        commands.entity(ghost).despawn();
+    }
+
+    let level = &selected.get(&levels).name;
+    let seed = level_config.seed;
+    recording.current = Some(GhostRun::new(level.clone(), seed));
+
+    // A loaded ghost was picked on purpose, so it wins over the best run.
+    let mut ghost = best_runs.runs.get(&(level.clone(), seed));
+    if let Some(loaded) = &loaded.0 {
+        if loaded.level != *level {
+            warn!(
+                "Loaded ghost was recorded on \"{}\", not \"{level}\"; not showing it",
+                loaded.level
+            );
+        } else if loaded.seed != seed {
+            warn!(
+                "Loaded ghost was recorded with seed {}, not {seed}; not showing it",
+                loaded.seed
+            );
+        } else {
+            ghost = Some(loaded);
+        }
+    }
+    active.0 = ghost.cloned();
+    let Some(ghost) = &active.0 else {
+        return;
+    };
+
+    commands.spawn((
+        ReplayGhost,
+        Mesh3d(meshes.add(Sphere { radius: 0.5 })),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::from(css::LIGHT_CYAN).with_alpha(0.35),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        })),
+        Transform::from_translation(ghost.position_at(0.0).unwrap_or_default()),
+    ));
+}
+
+fn record_run(
+    mut recording: ResMut<Recording>,
+    run_timer: Res<RunTimer>,
+    player: Query<&GlobalTransform, With<ThirdPersonCameraTarget>>,
+) {
+    if !run_timer.running {
+        return;
+    }
+    let (Some(run), Ok(player)) = (recording.current.as_mut(), player.single()) else {
+        return;
+    };
+
+    let time = run_timer.elapsed.as_secs_f32();
+    if run
+        .samples
+        .last()
+        .is_none_or(|(last, _)| time - last >= SAMPLE_INTERVAL)
+    {
+        run.samples.push((time, player.translation()));
+    }
+}
+
+fn finish_recording(
+    mut recording: ResMut<Recording>,
+    mut best_runs: ResMut<BestRuns>,
+    run_timer: Res<RunTimer>,
+    buttons: Query<(&ReplayMenuAction, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    if let Some(mut run) = recording.current.take() {
+        run.time = run_timer.elapsed.as_secs_f32();
+        let key = (run.level.clone(), run.seed);
+        if best_runs
+            .runs
+            .get(&key)
+            .is_none_or(|best| run.time < best.time)
+        {
+            best_runs.runs.insert(key, run.clone());
+        }
+        recording.completed = Some(run);
+    }
+
+    // The export button still reads "Ghost Exported" from the previous win.
+    for (action, children) in buttons.iter() {
+        if let (ReplayMenuAction::Export, Ok(mut text)) = (action, text_query.get_mut(children[0]))
+        {
+            text.0 = "Export Ghost".to_string();
+        }
+    }
+}
+
+fn play_ghost(
+    active: Res<ActiveGhost>,
+    run_timer: Res<RunTimer>,
+    mut ghosts: Query<&mut Transform, With<ReplayGhost>>,
+) {
+    let Some(position) = active
+        .0
+        .as_ref()
+        .and_then(|ghost| ghost.position_at(run_timer.elapsed.as_secs_f32()))
+    else {
+        return;
+    };
+
+    for mut transform in ghosts.iter_mut() {
+        transform.translation = position;
+    }
+}
+
+fn despawn_ghost(mut commands: Commands, ghosts: Query<Entity, With<ReplayGhost>>) {
+    for ghost in ghosts.iter() {
+        commands.entity(ghost).despawn();
+    }
+}
+
+fn save_best_runs(best_runs: Res<BestRuns>) {
+    storage::save(BEST_RUNS_PATH, &*best_runs);
+}
+
+fn replay_button_system(
+    interaction_query: Query<
+        (&Interaction, &ReplayMenuAction, &Children),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut text_query: Query<&mut Text>,
+    mut loaded: ResMut<LoadedGhost>,
+    mut level_config: ResMut<LevelConfig>,
+    recording: Res<Recording>,
+) {
+    for (interaction, action, children) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let label = match *action {
+            ReplayMenuAction::Export => export_ghost(&recording),
+            ReplayMenuAction::Import => import_ghost(&mut loaded, &mut level_config),
+        };
+        if let Ok(mut text) = text_query.get_mut(children[0]) {
+            text.0 = label;
+        }
+    }
+}
+
+fn export_ghost(recording: &Recording) -> String {
+    match &recording.completed {
+        Some(run) => {
+            storage::save(GHOST_PATH, run);
+            "Ghost Exported".to_string()
+        }
+        None => "No Run To Export".to_string(),
+    }
+}
+
+/// Also switches to the ghost's layout, so it can be raced straight away.
+fn import_ghost(loaded: &mut LoadedGhost, level_config: &mut LevelConfig) -> String {
+    match storage::read::<GhostRun>(GHOST_PATH) {
+        Ok(run) if run.version != GHOST_VERSION => {
+            warn!(
+                "{GHOST_PATH} is version {}, expected {GHOST_VERSION}",
+                run.version
+            );
+            "Unsupported Ghost".to_string()
+        }
+        Ok(run) => {
+            let label = format!("Ghost: {} {:.2}s", run.level, run.time);
+            level_config.seed = run.seed;
+            loaded.0 = Some(run);
+            label
+        }
+        Err(err) => {
+            warn!("Could not load {GHOST_PATH}: {err}");
+            "No Ghost Found".to_string()
+        }
+    }
+}
+
+pub fn replay_button(
+    label: &str,
+    action: ReplayMenuAction,
+    assets: &FontAssets,
+) -> impl Bundle + use<> {
+    (
+        Button,
+        action,
+        Node {
+            width: Val::Px(300.0),
+            height: Val::Px(80.0),
+            border: UiRect::all(Val::Px(5.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BorderColor(Color::BLACK),
+        BorderRadius::MAX,
+        BackgroundColor(NORMAL_BUTTON),
+        children![(
+            Text::new(label),
+            TextFont {
+                font: assets.u_atom.clone(),
+                font_size: 32.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+            TextShadow::default(),
+        )],
+    )
+}
@@ -0,0 +1,424 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_tnua::TnuaToggle;
+
+use crate::combo::{Combo, ComboShield};
+use crate::controls::KeyBindings;
+use crate::countdown::{RunStarted, RunTimer};
+use crate::objective::{Collected, Objective};
+use crate::settings::Settings;
+use crate::stats::{Stats, UnsavedAtoms};
+use crate::{
+    AtomAssets, AtomCollected, AtomCollectionSet, AtomValue, GameState, Player, spawn_atom,
+};
+
+/// Lets the player undo the last few seconds. Every fixed tick the player's
+/// state goes into a ring buffer; on rewind the buffer is played back in
+/// reverse, and any atoms collected in that window are put back along with
+/// the score, combo and shields they earned.
+pub struct RewindPlugin;
+
+impl Plugin for RewindPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RewindConfig>()
+            .init_resource::<Rewind>()
+            .add_systems(
+                Update,
+                (
+                    reset_rewind.run_if(on_event::<RunStarted>),
+                    start_rewind.run_if(in_state(GameState::Game)),
+                )
+                    .chain(),
+            )
+            // Each snapshot is taken before the tick's pickups, which are
+            // logged after them against the same run time, so a rewind to a
+            // snapshot puts back exactly the atoms its score doesn't include.
+            .add_systems(
+                FixedUpdate,
+                (
+                    (record_history, play_rewind)
+                        .chain()
+                        .before(AtomCollectionSet),
+                    log_collected_atoms.after(AtomCollectionSet),
+                )
+                    .run_if(in_state(GameState::Game)),
+            );
+    }
+}
+
+#[derive(Resource)]
+pub struct RewindConfig {
+    /// How far back a rewind goes. Playback runs at normal speed, so it also
+    /// takes this long.
+    pub duration: Duration,
+    /// Time after a rewind finishes before the next one is allowed.
+    pub cooldown: Duration,
+}
+
+impl Default for RewindConfig {
+    fn default() -> Self {
+        Self {
+            duration: Duration::from_secs(3),
+            cooldown: Duration::from_secs(10),
+        }
+    }
+}
+
+struct Snapshot {
+    /// Run time the snapshot was taken at.
+    time: Duration,
+    position: Vec3,
+    rotation: Quat,
+    linear: Vec3,
+    angular: Vec3,
+    combo_count: u32,
+    combo_timer: Timer,
+    score: u32,
+    shields: u32,
+}
+
+#[derive(Resource, Default)]
+struct Rewind {
+    history: VecDeque<Snapshot>,
+    /// Atoms collected since the oldest snapshot, with the run time.
+    collected: VecDeque<(Duration, AtomCollected)>,
+    cooldown: Option<Timer>,
+    playing: bool,
+}
+
+fn reset_rewind(mut rewind: ResMut<Rewind>) {
+    *rewind = Rewind::default();
+}
+
+fn log_collected_atoms(
+    mut rewind: ResMut<Rewind>,
+    mut collected: EventReader<AtomCollected>,
+    run_timer: Res<RunTimer>,
+) {
+    for event in collected.read() {
+        rewind
+            .collected
+            .push_back((run_timer.elapsed, event.clone()));
+    }
+
+    // Kept as far back as the oldest snapshot rather than the run time, which
+    // carries on during playback and would drop the atoms being rewound.
+    let Some(horizon) = rewind.history.front().map(|snapshot| snapshot.time) else {
+        return;
+    };
+    while rewind
+        .collected
+        .front()
+        .is_some_and(|(time, _)| *time < horizon)
+    {
+        rewind.collected.pop_front();
+    }
+}
+
+fn record_history(
+    mut rewind: ResMut<Rewind>,
+    run_timer: Res<RunTimer>,
+    config: Res<RewindConfig>,
+    combo: Res<Combo>,
+    player: Query<
+        (
+            &Position,
+            &Rotation,
+            &LinearVelocity,
+            &AngularVelocity,
+            &ComboShield,
+        ),
+        With<Player>,
+    >,
+) {
+    if rewind.playing || !run_timer.running {
+        return;
+    }
+    let Ok((position, rotation, linear, angular, shield)) = player.single() else {
+        return;
+    };
+
+    rewind.history.push_back(Snapshot {
+        time: run_timer.elapsed,
+        position: position.0,
+        rotation: rotation.0,
+        linear: linear.0,
+        angular: angular.0,
+        combo_count: combo.count,
+        combo_timer: combo.timer.clone(),
+        score: combo.score,
+        shields: shield.count,
+    });
+
+    let horizon = run_timer.elapsed.saturating_sub(config.duration);
+    while rewind
+        .history
+        .front()
+        .is_some_and(|snapshot| snapshot.time < horizon)
+    {
+        rewind.history.pop_front();
+    }
+}
+
+fn start_rewind(
+    mut commands: Commands,
+    mut rewind: ResMut<Rewind>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    run_timer: Res<RunTimer>,
+    time: Res<Time>,
+    player: Query<Entity, With<Player>>,
+) {
+    if let Some(cooldown) = rewind.cooldown.as_mut()
+        && cooldown.tick(time.delta()).finished()
+    {
+        rewind.cooldown = None;
+    }
+
+    if !keyboard.just_pressed(bindings.rewind)
+        || !run_timer.running
+        || rewind.playing
+        || rewind.cooldown.is_some()
+        || rewind.history.is_empty()
+    {
+        return;
+    }
+    let Ok(player) = player.single() else {
+        return;
+    };
+
+    // Playback moves the ball directly, so physics and Tnua stay out of it.
+    commands
+        .entity(player)
+        .insert((RigidBody::Kinematic, TnuaToggle::Disabled));
+    rewind.playing = true;
+}
+
+fn play_rewind(
+    mut commands: Commands,
+    mut rewind: ResMut<Rewind>,
+    mut objective: ResMut<Objective>,
+    mut atoms_collected: ResMut<Collected>,
+    mut combo: ResMut<Combo>,
+    mut unsaved_atoms: ResMut<UnsavedAtoms>,
+    mut stats: ResMut<Stats>,
+    config: Res<RewindConfig>,
+    settings: Res<Settings>,
+    atom_assets: Res<AtomAssets>,
+    mut player: Query<
+        (
+            Entity,
+            &mut Position,
+            &mut Rotation,
+            &mut LinearVelocity,
+            &mut AngularVelocity,
+            &mut ComboShield,
+        ),
+        With<Player>,
+    >,
+) {
+    if !rewind.playing {
+        return;
+    }
+    let Ok((entity, mut position, mut rotation, mut linear, mut angular, mut shield)) =
+        player.single_mut()
+    else {
+        return;
+    };
+
+    let Some(snapshot) = rewind.history.pop_back() else {
+        return;
+    };
+    position.0 = snapshot.position;
+    rotation.0 = snapshot.rotation;
+    linear.0 = Vec3::ZERO;
+    angular.0 = Vec3::ZERO;
+
+    if !rewind.history.is_empty() {
+        return;
+    }
+
+    // Reached the oldest snapshot: hand the ball back to physics with the
+    // motion it had at that moment, and put back what was collected since,
+    // so none of it can be collected twice.
+    linear.0 = snapshot.linear;
+    angular.0 = snapshot.angular;
+    commands
+        .entity(entity)
+        .insert((RigidBody::Dynamic, TnuaToggle::Enabled));
+
+    combo.count = snapshot.combo_count;
+    combo.timer = snapshot.combo_timer;
+    combo.score = snapshot.score;
+    shield.count = snapshot.shields;
+
+    let returned: Vec<AtomCollected> = rewind
+        .collected
+        .drain(..)
+        .filter(|(time, _)| *time >= snapshot.time)
+        .map(|(_, atom)| atom)
+        .collect();
+    for atom in &returned {
+        let spawned = spawn_atom(&mut commands, &atom_assets, &settings, atom.position);
+        let mut spawned = commands.entity(spawned);
+        spawned.insert(AtomValue(atom.value));
+        if let Some(moving) = &atom.moving {
+            spawned.insert((moving.clone(), RigidBody::Kinematic));
+        }
+        if let Some(timed) = &atom.bonus {
+            spawned.insert(timed.clone());
+        } else {
+            objective.uncollect();
+            atoms_collected.0 = atoms_collected.0.saturating_sub(1);
+        }
+    }
+
+    // Off the lifetime total too, from what's already been saved if need be.
+    let returned = returned.len() as u64;
+    let unsaved = returned.min(unsaved_atoms.0);
+    unsaved_atoms.0 -= unsaved;
+    if returned > unsaved {
+        stats.atoms_collected = stats.atoms_collected.saturating_sub(returned - unsaved);
+    }
+
+    rewind.playing = false;
+    rewind.cooldown = Some(Timer::new(config.cooldown, TimerMode::Once));
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::input::ButtonState;
+    use bevy::input::InputPlugin;
+    use bevy::input::keyboard::{Key, KeyboardInput, NativeKey};
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+
+    const STEP: Duration = Duration::from_millis(50);
+
+    fn key(app: &mut App, state: ButtonState) {
+        app.world_mut().send_event(KeyboardInput {
+            key_code: KeyBindings::default().rewind,
+            logical_key: Key::Unidentified(NativeKey::Unidentified),
+            state,
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+    }
+
+    /// Stands in for `collision_response`: collects every atom, once, when
+    /// the run is a fifth of a second in.
+    fn collect_atoms(
+        mut commands: Commands,
+        mut collected: EventWriter<AtomCollected>,
+        mut objective: ResMut<Objective>,
+        mut atoms_collected: ResMut<Collected>,
+        mut combo: ResMut<Combo>,
+        mut unsaved_atoms: ResMut<UnsavedAtoms>,
+        run_timer: Res<RunTimer>,
+        atoms: Query<(Entity, &Transform, &AtomValue)>,
+        mut done: Local<bool>,
+    ) {
+        if *done || run_timer.elapsed < Duration::from_millis(200) {
+            return;
+        }
+        *done = true;
+        for (entity, transform, value) in &atoms {
+            commands.entity(entity).despawn();
+            objective.collect();
+            atoms_collected.0 += 1;
+            combo.count += 1;
+            combo.score += value.0;
+            unsaved_atoms.0 += 1;
+            collected.write(AtomCollected {
+                position: transform.translation,
+                value: value.0,
+                bonus: None,
+                moving: None,
+            });
+        }
+    }
+
+    #[test]
+    fn rewind_puts_back_collected_atoms() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, InputPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(STEP))
+            .insert_resource(Time::<Fixed>::from_duration(STEP))
+            .insert_resource(RewindConfig {
+                duration: Duration::from_millis(500),
+                ..default()
+            })
+            .insert_resource(Objective::new(2, 1.0))
+            .insert_resource(RunTimer {
+                running: true,
+                ..default()
+            })
+            .insert_resource(AtomAssets {
+                u_atom: Handle::default(),
+            })
+            .init_resource::<Settings>()
+            .init_resource::<KeyBindings>()
+            .init_resource::<Collected>()
+            .init_resource::<Combo>()
+            .init_resource::<UnsavedAtoms>()
+            .init_resource::<Stats>()
+            .add_event::<RunStarted>()
+            .add_event::<AtomCollected>()
+            .insert_state(GameState::Game)
+            .add_plugins(RewindPlugin)
+            .add_systems(
+                Update,
+                |mut run_timer: ResMut<RunTimer>, time: Res<Time>| {
+                    run_timer.elapsed += time.delta();
+                },
+            )
+            .add_systems(FixedUpdate, collect_atoms.in_set(AtomCollectionSet));
+
+        let position = Vec3::new(5.0, 0.0, -2.0);
+        app.world_mut().spawn((
+            Player,
+            Position::default(),
+            Rotation::default(),
+            LinearVelocity::default(),
+            AngularVelocity::default(),
+            ComboShield::default(),
+        ));
+        app.world_mut()
+            .spawn((Transform::from_translation(position), AtomValue(3)));
+
+        // A full window of history, with the atom collected part way in.
+        for _ in 0..12 {
+            app.update();
+        }
+        assert_eq!(app.world().resource::<Combo>().score, 3);
+        let atoms = |app: &mut App| {
+            app.world_mut()
+                .query::<(&Transform, &AtomValue)>()
+                .iter(app.world())
+                .map(|(transform, value)| (transform.translation, value.0))
+                .collect::<Vec<_>>()
+        };
+        assert!(atoms(&mut app).is_empty());
+
+        key(&mut app, ButtonState::Pressed);
+        app.update();
+        key(&mut app, ButtonState::Released);
+        // Playback takes as long as the window it undoes.
+        for _ in 0..12 {
+            app.update();
+        }
+        assert!(!app.world().resource::<Rewind>().playing);
+
+        assert_eq!(atoms(&mut app), [(position, 3)]);
+        assert_eq!(app.world().resource::<Objective>().collected, 0);
+        assert_eq!(app.world().resource::<Collected>().0, 0);
+        assert_eq!(app.world().resource::<Combo>().score, 0);
+        assert_eq!(app.world().resource::<UnsavedAtoms>().0, 0);
+    }
+}
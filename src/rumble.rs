@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+use bevy::input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest};
+use bevy::prelude::*;
+
+use crate::settings::Settings;
+use crate::{AtomCollected, GameOver, GameState, PendingGameOver};
+
+/// Shakes any connected gamepad when an atom goes off, and harder for the
+/// final blast. Turned off by `Settings::rumble`.
+pub struct RumblePlugin;
+
+impl Plugin for RumblePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RumbleConfig>().add_systems(
+            Update,
+            (
+                atom_rumble.run_if(on_event::<AtomCollected>),
+                blast_rumble.run_if(on_event::<GameOver>),
+            )
+                .run_if(rumble_enabled),
+        );
+    }
+}
+
+#[derive(Resource)]
+pub struct RumbleConfig {
+    /// Strength of each collected atom's rumble, from 0 to 1.
+    pub atom_intensity: f32,
+    pub atom_duration: Duration,
+    pub blast_intensity: f32,
+    pub blast_duration: Duration,
+}
+
+impl Default for RumbleConfig {
+    fn default() -> Self {
+        Self {
+            atom_intensity: 0.3,
+            atom_duration: Duration::from_millis(120),
+            blast_intensity: 1.0,
+            blast_duration: Duration::from_millis(700),
+        }
+    }
+}
+
+fn rumble_enabled(settings: Res<Settings>) -> bool {
+    settings.rumble
+}
+
+// Only connected gamepads have a `Gamepad` entity, so without one nothing is
+// sent.
+fn rumble(
+    requests: &mut EventWriter<GamepadRumbleRequest>,
+    gamepads: &Query<Entity, With<Gamepad>>,
+    intensity: f32,
+    duration: Duration,
+) {
+    for gamepad in gamepads.iter() {
+        requests.write(GamepadRumbleRequest::Add {
+            gamepad,
+            intensity: GamepadRumbleIntensity {
+                strong_motor: intensity,
+                weak_motor: intensity,
+            },
+            duration,
+        });
+    }
+}
+
+fn atom_rumble(
+    mut collected: EventReader<AtomCollected>,
+    config: Res<RumbleConfig>,
+    gamepads: Query<Entity, With<Gamepad>>,
+    mut requests: EventWriter<GamepadRumbleRequest>,
+) {
+    // Atoms going off together make one rumble rather than a pile-up.
+    if collected.read().count() > 0 {
+        rumble(
+            &mut requests,
+            &gamepads,
+            config.atom_intensity,
+            config.atom_duration,
+        );
+    }
+}
+
+/// The detonator keeps reporting contact while the player is flung off it,
+/// so only the game over that starts the blast counts.
+fn blast_rumble(
+    mut game_over: EventReader<GameOver>,
+    pending: Option<Res<PendingGameOver>>,
+    config: Res<RumbleConfig>,
+    gamepads: Query<Entity, With<Gamepad>>,
+    mut requests: EventWriter<GamepadRumbleRequest>,
+) {
+    let win = game_over.read().any(|event| event.0 == GameState::Win);
+    if win && pending.is_none() {
+        rumble(
+            &mut requests,
+            &gamepads,
+            config.blast_intensity,
+            config.blast_duration,
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_third_person_camera::ThirdPersonCameraTarget;
+
+use crate::settings::{GraphicsQuality, Settings};
+use crate::{GameState, LevelEntity};
+
+const SCORCH_SPACING: f32 = 1.5;
+const SCORCH_MIN_SPEED: f32 = 10.0;
+const SCORCH_LIFETIME_SECS: f32 = 6.0;
+const SCORCH_COLOR: Color = Color::srgb(0.25, 0.9, 0.2);
+
+/// Leaves fading radioactive scorch marks on the ground behind a fast player.
+/// They're cleared when the run ends, and are level entities besides, so
+/// none carry over into the next run however it's left.
+pub struct ScorchPlugin;
+
+impl Plugin for ScorchPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (spawn_scorch, fade_scorch).run_if(in_state(GameState::Game)),
+        )
+        .add_systems(OnEnter(GameState::Win), clear_scorch)
+        .add_systems(OnEnter(GameState::Lose), clear_scorch);
+    }
+}
+
+#[derive(Component)]
+struct Scorch {
+    timer: Timer,
+    material: Handle<StandardMaterial>,
+}
+
+fn max_scorch_marks(quality: GraphicsQuality) -> usize {
+    match quality {
+        GraphicsQuality::Low => 0,
+        GraphicsQuality::Medium => 64,
+        GraphicsQuality::High => 192,
+    }
+}
+
+fn spawn_scorch(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut last_position: Local<Option<Vec3>>,
+    settings: Res<Settings>,
+    player: Query<(&GlobalTransform, &LinearVelocity), With<ThirdPersonCameraTarget>>,
+    scorch_query: Query<(Entity, &Scorch)>,
+) {
+    let Ok((transform, velocity)) = player.single() else {
+        *last_position = None;
+        return;
+    };
+
+    let max_marks = max_scorch_marks(settings.graphics_quality);
+    if !settings.scorch_trail || max_marks == 0 {
+        return;
+    }
+
+    let ground_position = transform.translation().with_y(0.01);
+    let far_enough =
+        last_position.is_none_or(|last| last.distance(ground_position) >= SCORCH_SPACING);
+    if !far_enough || velocity.length() < SCORCH_MIN_SPEED {
+        return;
+    }
+    *last_position = Some(ground_position);
+
+    // Drop the oldest marks first so the trail never grows past the cap.
+    let count = scorch_query.iter().count();
+    if count >= max_marks {
+        let mut oldest: Vec<_> = scorch_query.iter().collect();
+        oldest.sort_by_key(|(_, scorch)| std::cmp::Reverse(scorch.timer.elapsed()));
+        for (entity, _) in oldest.into_iter().take(count + 1 - max_marks) {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    let material = materials.add(StandardMaterial {
+        base_color: SCORCH_COLOR.with_alpha(0.6),
+        emissive: LinearRgba::from(SCORCH_COLOR) * 0.5,
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+
+    commands.spawn((
+        Mesh3d(meshes.add(Circle::new(0.6))),
+        MeshMaterial3d(material.clone()),
+        Transform::from_translation(ground_position)
+            .with_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)),
+        Scorch {
+            timer: Timer::from_seconds(SCORCH_LIFETIME_SECS, TimerMode::Once),
+            material,
+        },
+        LevelEntity,
+    ));
+}
+
+fn fade_scorch(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut scorch_query: Query<(Entity, &mut Scorch)>,
+    time: Res<Time>,
+) {
+    for (entity, mut scorch) in scorch_query.iter_mut() {
+        scorch.timer.tick(time.delta());
+
+        if scorch.timer.finished() {
+            commands.entity(entity).despawn();
+        } else if let Some(material) = materials.get_mut(&scorch.material) {
+            let alpha = 0.6 * scorch.timer.fraction_remaining();
+            material.base_color.set_alpha(alpha);
+        }
+    }
+}
+
+fn clear_scorch(mut commands: Commands, scorch_query: Query<Entity, With<Scorch>>) {
+    for entity in scorch_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+use bevy_third_person_camera::ThirdPersonCamera;
+use rand::Rng;
+
+use crate::GameState;
+use crate::settings::Settings;
+
+/// Offset at full trauma, in world units.
+const MAX_OFFSET: f32 = 0.6;
+/// Roll at full trauma, in radians.
+const MAX_ROLL: f32 = 0.05;
+/// Trauma lost per second, so a full shake dies out in half a second.
+const DECAY: f32 = 2.0;
+
+/// Jolts the game camera when atoms go off. The third person camera keeps
+/// its orbit in the camera's own transform, so the shake is added just
+/// before transforms propagate and taken off again at the start of the next
+/// frame.
+pub struct ScreenShakePlugin;
+
+impl Plugin for ScreenShakePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScreenShake>()
+            .add_systems(First, remove_shake)
+            .add_systems(
+                PostUpdate,
+                apply_shake
+                    .run_if(in_state(GameState::Game))
+                    .before(TransformSystem::TransformPropagate),
+            )
+            .add_systems(OnExit(GameState::Game), reset_shake);
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ScreenShake {
+    /// From 0 to 1. The shake grows with its square, so small bumps stay
+    /// subtle.
+    pub trauma: f32,
+    /// What `apply_shake` did to the camera this frame.
+    applied: Option<(Vec3, Quat)>,
+}
+
+impl ScreenShake {
+    pub fn add(&mut self, trauma: f32) {
+        self.trauma = (self.trauma + trauma).min(1.0);
+    }
+}
+
+fn apply_shake(
+    mut shake: ResMut<ScreenShake>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+    mut camera: Single<&mut Transform, With<ThirdPersonCamera>>,
+) {
+    // Dropped outright, so turning the setting off mid-shake doesn't pick
+    // up where it left off.
+    if settings.reduce_motion {
+        shake.trauma = 0.0;
+    }
+    if shake.trauma <= 0.0 {
+        return;
+    }
+    let amount = shake.trauma * shake.trauma;
+    shake.trauma = (shake.trauma - DECAY * time.delta_secs()).max(0.0);
+
+    let mut rng = rand::rng();
+    let offset = camera.rotation
+        * Vec3::new(
+            rng.random_range(-1.0..1.0),
+            rng.random_range(-1.0..1.0),
+            0.0,
+        )
+        * MAX_OFFSET
+        * amount;
+    let roll: f32 = rng.random_range(-1.0..1.0);
+    let roll = Quat::from_rotation_z(roll * MAX_ROLL * amount);
+
+    camera.translation += offset;
+    camera.rotation *= roll;
+    shake.applied = Some((offset, roll));
+}
+
+fn remove_shake(
+    mut shake: ResMut<ScreenShake>,
+    mut camera: Query<&mut Transform, With<ThirdPersonCamera>>,
+) {
+    let Some((offset, roll)) = shake.applied.take() else {
+        return;
+    };
+    if let Ok(mut camera) = camera.single_mut() {
+        camera.translation -= offset;
+        camera.rotation *= roll.inverse();
+    }
+}
+
+fn reset_shake(mut shake: ResMut<ScreenShake>) {
+    shake.trauma = 0.0;
+}
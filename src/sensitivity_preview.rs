@@ -0,0 +1,220 @@
+use bevy::prelude::*;
+use bevy_third_person_camera::ThirdPersonCamera;
+
+use crate::settings_menu::SENSITIVITY_STEP;
+use crate::{CameraConfig, FontAssets, GameState, Menu, NORMAL_BUTTON};
+
+/// A sensitivity panel on the pause screen. Changes apply to the camera
+/// straight away and can be tried out by holding the right mouse button, but
+/// only stick once applied; leaving the pause screen otherwise reverts them.
+pub struct SensitivityPreviewPlugin;
+
+impl Plugin for SensitivityPreviewPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Pause), start_preview)
+            .add_systems(
+                Update,
+                (
+                    preview_button_system,
+                    apply_preview,
+                    test_look,
+                    preview_text,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Pause).and(resource_exists::<SensitivityPreview>)),
+            )
+            .add_systems(OnExit(GameState::Pause), end_preview);
+    }
+}
+
+#[derive(Resource)]
+struct SensitivityPreview {
+    pending: CameraConfig,
+}
+
+#[derive(Component)]
+struct PreviewPanel;
+
+#[derive(Component)]
+struct PreviewText;
+
+#[derive(Component, Clone, Copy)]
+enum PreviewAction {
+    Down,
+    Up,
+    Apply,
+    Revert,
+}
+
+fn start_preview(
+    mut commands: Commands,
+    camera_config: Res<CameraConfig>,
+    font_assets: Res<FontAssets>,
+    camera: Single<Entity, With<ThirdPersonCamera>>,
+) {
+    commands.insert_resource(SensitivityPreview {
+        pending: camera_config.clone(),
+    });
+
+    commands.spawn((
+        PreviewPanel,
+        // Lets the keyboard and gamepad reach it along with the pause menu.
+        Menu {
+            show_state: GameState::Pause,
+        },
+        UiTargetCamera(*camera),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(40.0),
+            width: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(10.0),
+            ..default()
+        },
+        children![
+            (
+                Node {
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(20.0),
+                    ..default()
+                },
+                children![
+                    preview_button("-", PreviewAction::Down, 60.0, &font_assets),
+                    (
+                        PreviewText,
+                        Text::default(),
+                        preview_font(&font_assets),
+                        TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                        TextShadow::default(),
+                    ),
+                    preview_button("+", PreviewAction::Up, 60.0, &font_assets),
+                ],
+            ),
+            (
+                Node {
+                    column_gap: Val::Px(20.0),
+                    ..default()
+                },
+                children![
+                    preview_button("Apply", PreviewAction::Apply, 160.0, &font_assets),
+                    preview_button("Revert", PreviewAction::Revert, 160.0, &font_assets),
+                ],
+            ),
+            (
+                Text::new("Hold right mouse button to try it out"),
+                TextFont {
+                    font: font_assets.u_atom.clone(),
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                TextShadow::default(),
+            ),
+        ],
+    ));
+}
+
+fn preview_font(assets: &FontAssets) -> TextFont {
+    TextFont {
+        font: assets.u_atom.clone(),
+        font_size: 30.0,
+        ..default()
+    }
+}
+
+fn preview_button(
+    label: &str,
+    action: PreviewAction,
+    width: f32,
+    assets: &FontAssets,
+) -> impl Bundle + use<> {
+    (
+        Button,
+        action,
+        Node {
+            width: Val::Px(width),
+            height: Val::Px(60.0),
+            border: UiRect::all(Val::Px(5.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BorderColor(Color::BLACK),
+        BorderRadius::MAX,
+        BackgroundColor(NORMAL_BUTTON),
+        children![(
+            Text::new(label),
+            preview_font(assets),
+            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+            TextShadow::default(),
+        )],
+    )
+}
+
+fn preview_button_system(
+    interaction_query: Query<(&Interaction, &PreviewAction), (Changed<Interaction>, With<Button>)>,
+    mut preview: ResMut<SensitivityPreview>,
+    mut camera_config: ResMut<CameraConfig>,
+) {
+    for (interaction, action) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match *action {
+            PreviewAction::Down => preview.pending.adjust_sensitivity(-SENSITIVITY_STEP),
+            PreviewAction::Up => preview.pending.adjust_sensitivity(SENSITIVITY_STEP),
+            PreviewAction::Apply => camera_config.sensitivity = preview.pending.sensitivity,
+            PreviewAction::Revert => preview.pending = camera_config.clone(),
+        }
+    }
+}
+
+fn apply_preview(preview: Res<SensitivityPreview>, mut camera: Single<&mut ThirdPersonCamera>) {
+    if preview.is_changed() {
+        camera.sensitivity = Vec2::splat(preview.pending.sensitivity);
+    }
+}
+
+/// Holding the right mouse button hands the mouse back to the camera, so the
+/// pending sensitivity can be felt without leaving the pause screen.
+fn test_look(mouse: Res<ButtonInput<MouseButton>>, mut camera: Single<&mut ThirdPersonCamera>) {
+    let testing = mouse.pressed(MouseButton::Right);
+    if camera.cursor_lock_active != testing {
+        camera.cursor_lock_active = testing;
+    }
+}
+
+fn preview_text(
+    preview: Res<SensitivityPreview>,
+    camera_config: Res<CameraConfig>,
+    mut text: Single<&mut Text, With<PreviewText>>,
+) {
+    if !preview.is_changed() && !camera_config.is_changed() {
+        return;
+    }
+
+    let pending = preview.pending.sensitivity;
+    text.0 = if pending == camera_config.sensitivity {
+        format!("Sensitivity: {pending:.1}")
+    } else {
+        format!("Sensitivity: {pending:.1} (not applied)")
+    };
+}
+
+fn end_preview(
+    mut commands: Commands,
+    camera_config: Res<CameraConfig>,
+    mut camera: Query<&mut ThirdPersonCamera>,
+    panel: Query<Entity, With<PreviewPanel>>,
+) {
+    for entity in panel.iter() {
+        commands.entity(entity).despawn();
+    }
+    commands.remove_resource::<SensitivityPreview>();
+
+    // Anything not applied is dropped.
+    for mut camera in camera.iter_mut() {
+        camera.sensitivity = Vec2::splat(camera_config.sensitivity);
+    }
+}
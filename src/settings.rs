@@ -0,0 +1,248 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::CameraConfig;
+use crate::controls::KeyBindings;
+use crate::display::DisplayConfig;
+use crate::graphics::GraphicsConfig;
+use crate::storage;
+use crate::theme::ColorPreset;
+
+const SETTINGS_PATH: &str = "settings.ron";
+
+/// Everything the player can configure, kept in one file. Loaded before any
+/// other plugin reads it, and written back whenever it changes.
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(storage::load::<Settings>(SETTINGS_PATH))
+            .init_resource::<DifficultyConfig>()
+            .add_systems(PreStartup, load_camera_config)
+            .add_systems(
+                Update,
+                (
+                    store_camera_config.run_if(resource_changed::<CameraConfig>),
+                    save_settings.run_if(resource_changed::<Settings>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Whether an action is active while its key is held, or flips on each press.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum InputMode {
+    Hold,
+    Toggle,
+}
+
+impl InputMode {
+    pub fn toggled(self) -> Self {
+        match self {
+            InputMode::Hold => InputMode::Toggle,
+            InputMode::Toggle => InputMode::Hold,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum GraphicsQuality {
+    Low,
+    Medium,
+    High,
+}
+
+impl GraphicsQuality {
+    const ALL: [GraphicsQuality; 3] = [
+        GraphicsQuality::Low,
+        GraphicsQuality::Medium,
+        GraphicsQuality::High,
+    ];
+
+    /// The next higher (positive `step`) or lower quality, wrapping round.
+    pub fn step(self, step: i32) -> Self {
+        let index = Self::ALL.iter().position(|q| *q == self).unwrap_or(0) as i32;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as i32) as usize]
+    }
+}
+
+/// How the player interacts with atoms they haven't collected yet.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum AtomPickup {
+    /// Atoms are solid; the player bounces off and collects by landing on them.
+    Bounce,
+    /// Atoms are sensors; the player rolls through and collects on overlap.
+    PassThrough,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    /// The next harder (positive `step`) or easier difficulty, wrapping round.
+    pub fn step(self, step: i32) -> Self {
+        let index = Self::ALL.iter().position(|d| *d == self).unwrap_or(0) as i32;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as i32) as usize]
+    }
+}
+
+/// How one difficulty scales the rest of the game. Chasers have their own
+/// tuning in `ChaserConfig`.
+pub struct DifficultyTuning {
+    /// Multiplies the player's walking and sprinting speed.
+    pub speed_factor: f32,
+    /// Multiplies `LevelConfig::atom_count` for scattered levels.
+    pub atom_factor: f32,
+    /// Fraction of the level's atoms needed for critical mass.
+    pub critical_mass: f32,
+    /// How fast the combo window and bonus atoms run down, where 1 is real
+    /// time.
+    pub timer_speed: f32,
+    /// Hazard hits the player can take, ending the run on the last.
+    pub health: u32,
+}
+
+#[derive(Resource)]
+pub struct DifficultyConfig {
+    pub easy: DifficultyTuning,
+    pub normal: DifficultyTuning,
+    pub hard: DifficultyTuning,
+}
+
+impl Default for DifficultyConfig {
+    fn default() -> Self {
+        Self {
+            easy: DifficultyTuning {
+                speed_factor: 0.85,
+                atom_factor: 0.75,
+                critical_mass: 0.5,
+                timer_speed: 0.7,
+                health: 5,
+            },
+            normal: DifficultyTuning {
+                speed_factor: 1.0,
+                atom_factor: 1.0,
+                critical_mass: 0.6,
+                timer_speed: 1.0,
+                health: 3,
+            },
+            hard: DifficultyTuning {
+                speed_factor: 1.2,
+                atom_factor: 1.5,
+                critical_mass: 0.75,
+                timer_speed: 1.5,
+                health: 1,
+            },
+        }
+    }
+}
+
+impl DifficultyConfig {
+    pub fn tuning(&self, difficulty: Difficulty) -> &DifficultyTuning {
+        match difficulty {
+            Difficulty::Easy => &self.easy,
+            Difficulty::Normal => &self.normal,
+            Difficulty::Hard => &self.hard,
+        }
+    }
+}
+
+#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub sprint_mode: InputMode,
+    pub cursor_lock_mode: InputMode,
+    pub graphics_quality: GraphicsQuality,
+    pub scorch_trail: bool,
+    pub atom_attraction: bool,
+    pub ghost_atoms: bool,
+    /// Read as atoms spawn, so a change from the pause menu applies from the
+    /// next run.
+    pub atom_pickup: AtomPickup,
+    pub combo_shields: bool,
+    pub atom_labels: bool,
+    /// End the run with a loss when the player falls off the world, instead
+    /// of respawning.
+    pub lose_on_fall: bool,
+    pub high_speed_stability: bool,
+    /// Tones down motion-heavy effects for players sensitive to them.
+    pub reduce_motion: bool,
+    /// Cut away to follow long chain reactions. Also off with
+    /// `reduce_motion`.
+    pub chain_camera: bool,
+    /// Turn the camera with the arrow keys, so no mouse is needed. Only with
+    /// one player: in two-player mode the arrows are player two's, and player
+    /// one still looks with the mouse.
+    pub keyboard_only: bool,
+    pub player_light: bool,
+    /// Move the sun through a day and night during each run.
+    pub day_night: bool,
+    /// Gamepad rumble when atoms go off.
+    pub rumble: bool,
+    pub difficulty: Difficulty,
+    /// Colours for players with red-green colour blindness.
+    pub color_theme: ColorPreset,
+    /// Spawn enemies that chase the player and end the run on contact.
+    pub chasers: bool,
+    /// Pause the run when the window loses focus, e.g. on alt-tab.
+    pub pause_on_focus_loss: bool,
+    pub display: DisplayConfig,
+    pub camera: CameraConfig,
+    pub graphics: GraphicsConfig,
+    pub key_bindings: KeyBindings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            sprint_mode: InputMode::Hold,
+            cursor_lock_mode: InputMode::Toggle,
+            graphics_quality: GraphicsQuality::High,
+            scorch_trail: true,
+            atom_attraction: false,
+            ghost_atoms: false,
+            atom_pickup: AtomPickup::Bounce,
+            combo_shields: true,
+            atom_labels: false,
+            lose_on_fall: false,
+            high_speed_stability: false,
+            reduce_motion: false,
+            chain_camera: true,
+            keyboard_only: false,
+            player_light: true,
+            day_night: true,
+            rumble: true,
+            difficulty: Difficulty::Normal,
+            color_theme: ColorPreset::Default,
+            chasers: false,
+            pause_on_focus_loss: true,
+            display: DisplayConfig::default(),
+            camera: CameraConfig::default(),
+            graphics: GraphicsConfig::default(),
+            key_bindings: KeyBindings::default(),
+        }
+    }
+}
+
+fn load_camera_config(mut commands: Commands, settings: Res<Settings>) {
+    commands.insert_resource(settings.camera.clone());
+}
+
+fn store_camera_config(config: Res<CameraConfig>, mut settings: ResMut<Settings>) {
+    if settings.camera != *config {
+        settings.camera = config.clone();
+    }
+}
+
+/// Also runs on the first frame, so a missing file is written out with the
+/// defaults.
+fn save_settings(settings: Res<Settings>) {
+    storage::save(SETTINGS_PATH, &*settings);
+}
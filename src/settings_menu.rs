@@ -0,0 +1,724 @@
+use bevy::prelude::*;
+use bevy::window::Monitor;
+
+use crate::controls::KeyBindings;
+use crate::coop::Players;
+use crate::display::{DisplayConfig, DisplayMode, plugged_in};
+use crate::graphics::GraphicsConfig;
+use crate::settings::{AtomPickup, Settings};
+use crate::stats::Stats;
+use crate::{CameraConfig, FontAssets, GameState, Menu, NORMAL_BUTTON};
+
+pub const SENSITIVITY_STEP: f32 = 0.1;
+const FOV_STEP: f32 = 5.0;
+
+/// The settings screen, reachable from the main menu and the pause menu.
+/// Changes apply as soon as they are made.
+pub struct SettingsMenuPlugin;
+
+impl Plugin for SettingsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettingsReturn>()
+            .add_systems(OnEnter(GameState::Settings), show_replay_intro)
+            .add_systems(
+                Update,
+                (
+                    setup_settings_menu.run_if(in_state(GameState::Menu).and(run_once)),
+                    settings_button_system.run_if(
+                        in_state(GameState::Menu)
+                            .or(in_state(GameState::Pause))
+                            .or(in_state(GameState::Settings)),
+                    ),
+                    (sensitivity_text, display_text, settings_text, players_text)
+                        .run_if(in_state(GameState::Settings)),
+                ),
+            );
+    }
+}
+
+/// The screen the settings were opened from, which Back returns to.
+#[derive(Resource)]
+pub struct SettingsReturn(pub GameState);
+
+impl Default for SettingsReturn {
+    fn default() -> Self {
+        Self(GameState::Menu)
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+pub enum SettingsMenuAction {
+    Open,
+    SensitivityDown,
+    SensitivityUp,
+    FovDown,
+    FovUp,
+    ToggleDisplayMode,
+    MonitorDown,
+    MonitorUp,
+    ResolutionDown,
+    ResolutionUp,
+    ToggleShadows,
+    ToggleVsync,
+    FpsLimitDown,
+    FpsLimitUp,
+    ToggleScorchTrail,
+    QualityDown,
+    QualityUp,
+    ToggleAtomLabels,
+    ToggleLoseOnFall,
+    ToggleHighSpeedStability,
+    ToggleReduceMotion,
+    ToggleChainCamera,
+    ToggleKeyboardOnly,
+    ToggleSprintMode,
+    ToggleCursorLockMode,
+    TogglePlayerLight,
+    ToggleDayNight,
+    ToggleRumble,
+    DifficultyDown,
+    DifficultyUp,
+    ColorThemeDown,
+    ColorThemeUp,
+    ToggleChasers,
+    ToggleGhostAtoms,
+    ToggleAtomPickup,
+    ToggleComboShields,
+    TogglePauseOnFocusLoss,
+    TogglePlayers,
+    ResetDefaults,
+    ReplayIntro,
+    ShowTutorial,
+    Back,
+}
+
+#[derive(Component)]
+struct SensitivityText;
+
+#[derive(Component)]
+struct FovText;
+
+#[derive(Component)]
+struct DisplayModeText;
+
+#[derive(Component)]
+struct MonitorText;
+
+#[derive(Component)]
+struct ResolutionText;
+
+#[derive(Component)]
+struct PlayersText;
+
+/// Only offered from the main menu, since the intro would start over a run
+/// that's still in progress.
+#[derive(Component)]
+struct ReplayIntroButton;
+
+/// A label showing a value from `Settings`, formatted by the function.
+#[derive(Component)]
+struct SettingText(fn(&Settings) -> String);
+
+fn settings_button_system(
+    interaction_query: Query<
+        (&Interaction, &SettingsMenuAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut camera_config: ResMut<CameraConfig>,
+    mut display_config: ResMut<DisplayConfig>,
+    mut graphics_config: ResMut<GraphicsConfig>,
+    mut settings: ResMut<Settings>,
+    mut key_bindings: ResMut<KeyBindings>,
+    mut players: ResMut<Players>,
+    mut stats: ResMut<Stats>,
+    mut settings_return: ResMut<SettingsReturn>,
+    monitors: Query<(Entity, &Monitor)>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, action) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match *action {
+            SettingsMenuAction::Open => {
+                settings_return.0 = state.get().clone();
+                next_state.set(GameState::Settings);
+            }
+            SettingsMenuAction::SensitivityDown => {
+                camera_config.adjust_sensitivity(-SENSITIVITY_STEP)
+            }
+            SettingsMenuAction::SensitivityUp => camera_config.adjust_sensitivity(SENSITIVITY_STEP),
+            SettingsMenuAction::FovDown => camera_config.adjust_fov(-FOV_STEP),
+            SettingsMenuAction::FovUp => camera_config.adjust_fov(FOV_STEP),
+            SettingsMenuAction::ToggleDisplayMode => display_config.toggle_mode(),
+            SettingsMenuAction::MonitorDown => {
+                display_config.step_monitor(-1, &plugged_in(monitors.iter()))
+            }
+            SettingsMenuAction::MonitorUp => {
+                display_config.step_monitor(1, &plugged_in(monitors.iter()))
+            }
+            SettingsMenuAction::ResolutionDown => display_config.step_resolution(-1),
+            SettingsMenuAction::ResolutionUp => display_config.step_resolution(1),
+            SettingsMenuAction::ToggleShadows => graphics_config.shadows = !graphics_config.shadows,
+            SettingsMenuAction::ToggleVsync => graphics_config.vsync = !graphics_config.vsync,
+            SettingsMenuAction::FpsLimitDown => graphics_config.step_fps_limit(-1),
+            SettingsMenuAction::FpsLimitUp => graphics_config.step_fps_limit(1),
+            SettingsMenuAction::ToggleScorchTrail => settings.scorch_trail = !settings.scorch_trail,
+            SettingsMenuAction::QualityDown => {
+                settings.graphics_quality = settings.graphics_quality.step(-1)
+            }
+            SettingsMenuAction::QualityUp => {
+                settings.graphics_quality = settings.graphics_quality.step(1)
+            }
+            SettingsMenuAction::ToggleAtomLabels => settings.atom_labels = !settings.atom_labels,
+            SettingsMenuAction::ToggleLoseOnFall => settings.lose_on_fall = !settings.lose_on_fall,
+            SettingsMenuAction::ToggleHighSpeedStability => {
+                settings.high_speed_stability = !settings.high_speed_stability
+            }
+            SettingsMenuAction::ToggleReduceMotion => {
+                settings.reduce_motion = !settings.reduce_motion
+            }
+            SettingsMenuAction::ToggleChainCamera => settings.chain_camera = !settings.chain_camera,
+            SettingsMenuAction::ToggleKeyboardOnly => {
+                settings.keyboard_only = !settings.keyboard_only
+            }
+            SettingsMenuAction::ToggleSprintMode => {
+                settings.sprint_mode = settings.sprint_mode.toggled()
+            }
+            SettingsMenuAction::ToggleCursorLockMode => {
+                settings.cursor_lock_mode = settings.cursor_lock_mode.toggled()
+            }
+            SettingsMenuAction::TogglePlayerLight => settings.player_light = !settings.player_light,
+            SettingsMenuAction::ToggleDayNight => settings.day_night = !settings.day_night,
+            SettingsMenuAction::ToggleRumble => settings.rumble = !settings.rumble,
+            SettingsMenuAction::DifficultyDown => {
+                settings.difficulty = settings.difficulty.step(-1)
+            }
+            SettingsMenuAction::DifficultyUp => settings.difficulty = settings.difficulty.step(1),
+            SettingsMenuAction::ColorThemeDown => {
+                settings.color_theme = settings.color_theme.step(-1)
+            }
+            SettingsMenuAction::ColorThemeUp => settings.color_theme = settings.color_theme.step(1),
+            SettingsMenuAction::ToggleChasers => settings.chasers = !settings.chasers,
+            SettingsMenuAction::ToggleGhostAtoms => settings.ghost_atoms = !settings.ghost_atoms,
+            SettingsMenuAction::ToggleAtomPickup => {
+                settings.atom_pickup = match settings.atom_pickup {
+                    AtomPickup::Bounce => AtomPickup::PassThrough,
+                    AtomPickup::PassThrough => AtomPickup::Bounce,
+                }
+            }
+            SettingsMenuAction::ToggleComboShields => {
+                settings.combo_shields = !settings.combo_shields
+            }
+            SettingsMenuAction::TogglePauseOnFocusLoss => {
+                settings.pause_on_focus_loss = !settings.pause_on_focus_loss
+            }
+            SettingsMenuAction::TogglePlayers => players.toggle(),
+            SettingsMenuAction::ResetDefaults => {
+                *settings = Settings::default();
+                *camera_config = CameraConfig::default();
+                *display_config = DisplayConfig::default();
+                *graphics_config = GraphicsConfig::default();
+                *key_bindings = KeyBindings::default();
+                *players = Players::default();
+            }
+            SettingsMenuAction::ReplayIntro => {
+                stats.has_played = false;
+                next_state.set(GameState::Intro);
+            }
+            SettingsMenuAction::ShowTutorial => stats.seen_tutorial = false,
+            SettingsMenuAction::Back => next_state.set(settings_return.0.clone()),
+        }
+    }
+}
+
+fn sensitivity_text(
+    camera_config: Res<CameraConfig>,
+    mut text_query: Query<&mut Text, With<SensitivityText>>,
+    mut fov_query: Query<&mut Text, (With<FovText>, Without<SensitivityText>)>,
+) {
+    if !camera_config.is_changed() {
+        return;
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.0 = format!("Mouse sensitivity: {:.1}", camera_config.sensitivity);
+    }
+    for mut text in fov_query.iter_mut() {
+        text.0 = format!("Field of view: {:.0}", camera_config.fov);
+    }
+}
+
+fn display_text(
+    display_config: Res<DisplayConfig>,
+    monitors: Query<(Entity, &Monitor)>,
+    added_monitors: Query<(), Added<Monitor>>,
+    mut removed_monitors: RemovedComponents<Monitor>,
+    mut mode_query: Query<&mut Text, With<DisplayModeText>>,
+    mut monitor_query: Query<&mut Text, (With<MonitorText>, Without<DisplayModeText>)>,
+    mut resolution_query: Query<
+        &mut Text,
+        (
+            With<ResolutionText>,
+            Without<DisplayModeText>,
+            Without<MonitorText>,
+        ),
+    >,
+) {
+    // A monitor coming or going can change the label too.
+    let monitors_changed = removed_monitors.read().count() > 0 || !added_monitors.is_empty();
+    if !display_config.is_changed() && !monitors_changed {
+        return;
+    }
+
+    let mode = match display_config.mode {
+        DisplayMode::Fullscreen => "Fullscreen",
+        DisplayMode::Windowed => "Windowed",
+    };
+    for mut text in mode_query.iter_mut() {
+        text.0 = format!("Display: {mode}");
+    }
+
+    let plugged_in = plugged_in(monitors.iter());
+    let monitor = match &display_config.monitor_id {
+        None => "Current".to_string(),
+        Some(id) => match (id.find(&plugged_in), &id.name) {
+            (Some(index), Some(name)) => format!("{} ({name})", index + 1),
+            (Some(index), None) => format!("{}", index + 1),
+            (None, Some(name)) => format!("{name} (unplugged)"),
+            (None, None) => "Unplugged".to_string(),
+        },
+    };
+    for mut text in monitor_query.iter_mut() {
+        text.0 = format!("Monitor: {monitor}");
+    }
+
+    let resolution = display_config.resolution;
+    for mut text in resolution_query.iter_mut() {
+        text.0 = format!("Resolution: {}x{}", resolution.x, resolution.y);
+    }
+}
+
+fn settings_text(settings: Res<Settings>, mut text_query: Query<(&mut Text, &SettingText)>) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for (mut text, label) in text_query.iter_mut() {
+        text.0 = label.0(&settings);
+    }
+}
+
+fn players_text(players: Res<Players>, mut text_query: Query<&mut Text, With<PlayersText>>) {
+    if !players.is_changed() {
+        return;
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.0 = format!("Players: {}", players.0);
+    }
+}
+
+fn show_replay_intro(
+    settings_return: Res<SettingsReturn>,
+    mut button: Query<&mut Node, With<ReplayIntroButton>>,
+) {
+    let display = if settings_return.0 == GameState::Menu {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    for mut node in button.iter_mut() {
+        node.display = display;
+    }
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value { "On" } else { "Off" }
+}
+
+fn setup_settings_menu(mut commands: Commands, font_assets: Res<FontAssets>) {
+    commands
+        .spawn((
+            Menu {
+                show_state: GameState::Settings,
+            },
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            Visibility::Hidden,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Settings"),
+                TextFont {
+                    font: font_assets.u_atom.clone(),
+                    font_size: 64.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextShadow::default(),
+            ));
+
+            parent
+                .spawn(Node {
+                    display: Display::Grid,
+                    // Three across, so all the rows fit on a 1080p screen.
+                    grid_template_columns: RepeatedGridTrack::auto(3),
+                    column_gap: Val::Px(40.0),
+                    row_gap: Val::Px(10.0),
+                    ..default()
+                })
+                .with_children(|grid| {
+                    // First, so it's found before the effects it tones down.
+                    grid.spawn(settings_row(
+                        SettingText(|settings| {
+                            format!("Reduce motion: {}", on_off(settings.reduce_motion))
+                        }),
+                        ("<", SettingsMenuAction::ToggleReduceMotion),
+                        (">", SettingsMenuAction::ToggleReduceMotion),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SensitivityText,
+                        ("-", SettingsMenuAction::SensitivityDown),
+                        ("+", SettingsMenuAction::SensitivityUp),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        FovText,
+                        ("-", SettingsMenuAction::FovDown),
+                        ("+", SettingsMenuAction::FovUp),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        DisplayModeText,
+                        ("<", SettingsMenuAction::ToggleDisplayMode),
+                        (">", SettingsMenuAction::ToggleDisplayMode),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        MonitorText,
+                        ("<", SettingsMenuAction::MonitorDown),
+                        (">", SettingsMenuAction::MonitorUp),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        ResolutionText,
+                        ("-", SettingsMenuAction::ResolutionDown),
+                        ("+", SettingsMenuAction::ResolutionUp),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| {
+                            format!("Shadows: {}", on_off(settings.graphics.shadows))
+                        }),
+                        ("<", SettingsMenuAction::ToggleShadows),
+                        (">", SettingsMenuAction::ToggleShadows),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| {
+                            format!("VSync: {}", on_off(settings.graphics.vsync))
+                        }),
+                        ("<", SettingsMenuAction::ToggleVsync),
+                        (">", SettingsMenuAction::ToggleVsync),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| match settings.graphics.fps_limit {
+                            Some(limit) => format!("FPS limit: {limit}"),
+                            None => "FPS limit: Off".to_string(),
+                        }),
+                        ("-", SettingsMenuAction::FpsLimitDown),
+                        ("+", SettingsMenuAction::FpsLimitUp),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| {
+                            format!("Effects quality: {:?}", settings.graphics_quality)
+                        }),
+                        ("-", SettingsMenuAction::QualityDown),
+                        ("+", SettingsMenuAction::QualityUp),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| {
+                            format!("Scorch trail: {}", on_off(settings.scorch_trail))
+                        }),
+                        ("<", SettingsMenuAction::ToggleScorchTrail),
+                        (">", SettingsMenuAction::ToggleScorchTrail),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| {
+                            format!("Atom labels: {}", on_off(settings.atom_labels))
+                        }),
+                        ("<", SettingsMenuAction::ToggleAtomLabels),
+                        (">", SettingsMenuAction::ToggleAtomLabels),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| {
+                            format!("Lose on fall: {}", on_off(settings.lose_on_fall))
+                        }),
+                        ("<", SettingsMenuAction::ToggleLoseOnFall),
+                        (">", SettingsMenuAction::ToggleLoseOnFall),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| {
+                            format!(
+                                "High-speed stability: {}",
+                                on_off(settings.high_speed_stability)
+                            )
+                        }),
+                        ("<", SettingsMenuAction::ToggleHighSpeedStability),
+                        (">", SettingsMenuAction::ToggleHighSpeedStability),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| {
+                            format!("Chain camera: {}", on_off(settings.chain_camera))
+                        }),
+                        ("<", SettingsMenuAction::ToggleChainCamera),
+                        (">", SettingsMenuAction::ToggleChainCamera),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| {
+                            format!("Keyboard only: {}", on_off(settings.keyboard_only))
+                        }),
+                        ("<", SettingsMenuAction::ToggleKeyboardOnly),
+                        (">", SettingsMenuAction::ToggleKeyboardOnly),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| format!("Sprint: {:?}", settings.sprint_mode)),
+                        ("<", SettingsMenuAction::ToggleSprintMode),
+                        (">", SettingsMenuAction::ToggleSprintMode),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| {
+                            format!("Cursor lock: {:?}", settings.cursor_lock_mode)
+                        }),
+                        ("<", SettingsMenuAction::ToggleCursorLockMode),
+                        (">", SettingsMenuAction::ToggleCursorLockMode),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| {
+                            format!("Player light: {}", on_off(settings.player_light))
+                        }),
+                        ("<", SettingsMenuAction::TogglePlayerLight),
+                        (">", SettingsMenuAction::TogglePlayerLight),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| {
+                            format!("Day/night cycle: {}", on_off(settings.day_night))
+                        }),
+                        ("<", SettingsMenuAction::ToggleDayNight),
+                        (">", SettingsMenuAction::ToggleDayNight),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| format!("Rumble: {}", on_off(settings.rumble))),
+                        ("<", SettingsMenuAction::ToggleRumble),
+                        (">", SettingsMenuAction::ToggleRumble),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| format!("Difficulty: {:?}", settings.difficulty)),
+                        ("-", SettingsMenuAction::DifficultyDown),
+                        ("+", SettingsMenuAction::DifficultyUp),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| format!("Colors: {:?}", settings.color_theme)),
+                        ("<", SettingsMenuAction::ColorThemeDown),
+                        (">", SettingsMenuAction::ColorThemeUp),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| format!("Chasers: {}", on_off(settings.chasers))),
+                        ("<", SettingsMenuAction::ToggleChasers),
+                        (">", SettingsMenuAction::ToggleChasers),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| {
+                            format!("Ghost atoms: {}", on_off(settings.ghost_atoms))
+                        }),
+                        ("<", SettingsMenuAction::ToggleGhostAtoms),
+                        (">", SettingsMenuAction::ToggleGhostAtoms),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| match settings.atom_pickup {
+                            AtomPickup::Bounce => "Atoms: Bounce off".to_string(),
+                            AtomPickup::PassThrough => "Atoms: Pass through".to_string(),
+                        }),
+                        ("<", SettingsMenuAction::ToggleAtomPickup),
+                        (">", SettingsMenuAction::ToggleAtomPickup),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| {
+                            format!("Combo shields: {}", on_off(settings.combo_shields))
+                        }),
+                        ("<", SettingsMenuAction::ToggleComboShields),
+                        (">", SettingsMenuAction::ToggleComboShields),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        SettingText(|settings| {
+                            format!(
+                                "Pause when unfocused: {}",
+                                on_off(settings.pause_on_focus_loss)
+                            )
+                        }),
+                        ("<", SettingsMenuAction::TogglePauseOnFocusLoss),
+                        (">", SettingsMenuAction::TogglePauseOnFocusLoss),
+                        &font_assets,
+                    ));
+
+                    grid.spawn(settings_row(
+                        PlayersText,
+                        ("<", SettingsMenuAction::TogglePlayers),
+                        (">", SettingsMenuAction::TogglePlayers),
+                        &font_assets,
+                    ));
+                });
+
+            parent.spawn(settings_button(
+                "Reset to Defaults",
+                SettingsMenuAction::ResetDefaults,
+                Val::Px(360.0),
+                &font_assets,
+            ));
+
+            parent.spawn((
+                ReplayIntroButton,
+                settings_button(
+                    "Replay Intro",
+                    SettingsMenuAction::ReplayIntro,
+                    Val::Px(300.0),
+                    &font_assets,
+                ),
+            ));
+
+            parent.spawn(settings_button(
+                "Show Tutorial",
+                SettingsMenuAction::ShowTutorial,
+                Val::Px(300.0),
+                &font_assets,
+            ));
+
+            parent.spawn(settings_button(
+                "Back",
+                SettingsMenuAction::Back,
+                Val::Px(300.0),
+                &font_assets,
+            ));
+        });
+}
+
+/// A value label between a pair of small buttons that adjust it.
+fn settings_row(
+    marker: impl Component,
+    (down_label, down): (&str, SettingsMenuAction),
+    (up_label, up): (&str, SettingsMenuAction),
+    assets: &FontAssets,
+) -> impl Bundle {
+    (
+        Node {
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(20.0),
+            ..default()
+        },
+        children![
+            settings_button(down_label, down, Val::Px(60.0), assets),
+            (
+                marker,
+                Text::default(),
+                TextFont {
+                    font: assets.u_atom.clone(),
+                    font_size: 30.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextShadow::default(),
+            ),
+            settings_button(up_label, up, Val::Px(60.0), assets),
+        ],
+    )
+}
+
+fn settings_button(
+    label: &str,
+    action: SettingsMenuAction,
+    width: Val,
+    assets: &FontAssets,
+) -> impl Bundle {
+    (
+        Button,
+        action,
+        Node {
+            width,
+            height: Val::Px(60.0),
+            border: UiRect::all(Val::Px(5.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BorderColor(Color::BLACK),
+        BorderRadius::MAX,
+        BackgroundColor(NORMAL_BUTTON),
+        children![(
+            Text::new(label),
+            TextFont {
+                font: assets.u_atom.clone(),
+                font_size: 30.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+            TextShadow::default(),
+        )],
+    )
+}
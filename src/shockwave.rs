@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use bevy::pbr::{NotShadowCaster, NotShadowReceiver};
+use bevy::prelude::*;
+
+use crate::settings::Settings;
+use crate::{GameState, LevelEntity};
+
+/// An expanding, fading sphere for the final blast, so its reach can be seen
+/// as well as heard.
+pub struct ShockwavePlugin;
+
+impl Plugin for ShockwavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShockwaveConfig>()
+            .add_systems(Update, update_shockwaves.run_if(in_state(GameState::Game)));
+    }
+}
+
+#[derive(Resource)]
+pub struct ShockwaveConfig {
+    pub duration: Duration,
+    pub color: Color,
+    /// Opacity at the start, fading to nothing.
+    pub alpha: f32,
+    /// Used instead with `Settings::reduce_motion`, so the blast doesn't
+    /// flash across the whole screen.
+    pub reduced_alpha: f32,
+}
+
+impl Default for ShockwaveConfig {
+    fn default() -> Self {
+        Self {
+            duration: Duration::from_secs(1),
+            color: Color::srgb(1.0, 0.7, 0.3),
+            alpha: 0.5,
+            reduced_alpha: 0.1,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct ShockwaveEffect {
+    timer: Timer,
+    /// Radius the sphere grows to.
+    radius: f32,
+    /// Opacity at the start.
+    alpha: f32,
+}
+
+/// Spawns a shockwave at `center` growing out to `radius`. Each one gets its
+/// own material so it can fade on its own.
+pub fn spawn_shockwave(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    config: &ShockwaveConfig,
+    settings: &Settings,
+    center: Vec3,
+    radius: f32,
+) {
+    let alpha = if settings.reduce_motion {
+        config.reduced_alpha
+    } else {
+        config.alpha
+    };
+    commands.spawn((
+        ShockwaveEffect {
+            timer: Timer::new(config.duration, TimerMode::Once),
+            radius,
+            alpha,
+        },
+        Mesh3d(meshes.add(Sphere::new(1.0))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: config.color.with_alpha(alpha),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            // The player is inside it for most of its life.
+            cull_mode: None,
+            ..default()
+        })),
+        Transform::from_translation(center).with_scale(Vec3::ZERO),
+        NotShadowCaster,
+        NotShadowReceiver,
+        LevelEntity,
+    ));
+}
+
+fn update_shockwaves(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<ShockwaveConfig>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut shockwaves: Query<(
+        Entity,
+        &mut ShockwaveEffect,
+        &mut Transform,
+        &MeshMaterial3d<StandardMaterial>,
+    )>,
+) {
+    for (entity, mut shockwave, mut transform, material) in shockwaves.iter_mut() {
+        if shockwave.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let progress = shockwave.timer.fraction();
+        // Fast at first and slowing, like a real blast front.
+        let eased = 1.0 - (1.0 - progress).powi(2);
+        transform.scale = Vec3::splat(shockwave.radius * eased);
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.base_color = config.color.with_alpha(shockwave.alpha * (1.0 - progress));
+        }
+    }
+}
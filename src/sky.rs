@@ -0,0 +1,112 @@
+use bevy::asset::RenderAssetUsages;
+use bevy::color::ColorToPacked;
+use bevy::core_pipeline::Skybox;
+use bevy::prelude::*;
+use bevy::render::render_resource::{
+    Extent3d, TextureDimension, TextureFormat, TextureViewDescriptor, TextureViewDimension,
+};
+
+/// Pixels along each edge of a cube face. The gradient is smooth enough that
+/// this is plenty.
+const FACE_SIZE: u32 = 64;
+
+/// A gradient sky drawn behind the level, from the ground colour below the
+/// horizon up to a deeper blue overhead. It's built in code rather than
+/// loaded, so there's no file to go missing. The game camera still clears to
+/// a solid colour underneath, which shows until the cubemap reaches the GPU.
+pub struct SkyPlugin;
+
+impl Plugin for SkyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SkyConfig>()
+            .add_systems(Startup, build_sky);
+    }
+}
+
+#[derive(Resource)]
+pub struct SkyConfig {
+    pub zenith: Color,
+    pub horizon: Color,
+    pub ground: Color,
+    /// Skybox brightness in full daylight, in candela per square metre.
+    pub day_brightness: f32,
+    pub night_brightness: f32,
+}
+
+impl Default for SkyConfig {
+    fn default() -> Self {
+        Self {
+            zenith: Color::srgb(0.25, 0.45, 0.8),
+            horizon: Color::srgb(0.75, 0.82, 0.9),
+            ground: Color::srgb(0.35, 0.35, 0.38),
+            day_brightness: 1000.0,
+            night_brightness: 60.0,
+        }
+    }
+}
+
+/// Images made at startup for the game camera's surroundings.
+#[derive(Resource)]
+pub struct EnvAssets {
+    pub sky: Handle<Image>,
+}
+
+impl EnvAssets {
+    pub fn skybox(&self, config: &SkyConfig) -> Skybox {
+        Skybox {
+            image: self.sky.clone(),
+            brightness: config.day_brightness,
+            ..default()
+        }
+    }
+}
+
+fn build_sky(mut commands: Commands, mut images: ResMut<Assets<Image>>, config: Res<SkyConfig>) {
+    let mut data = Vec::with_capacity((FACE_SIZE * FACE_SIZE * 6 * 4) as usize);
+    // Faces in the order +X, -X, +Y, -Y, +Z, -Z. Only the height of each
+    // texel's direction matters.
+    for face in 0..6 {
+        for row in 0..FACE_SIZE {
+            for column in 0..FACE_SIZE {
+                let u = (column as f32 + 0.5) / FACE_SIZE as f32 * 2.0 - 1.0;
+                let v = (row as f32 + 0.5) / FACE_SIZE as f32 * 2.0 - 1.0;
+                let height = match face {
+                    2 => 1.0,
+                    3 => -1.0,
+                    _ => -v / (1.0 + u * u + v * v).sqrt(),
+                };
+                data.extend_from_slice(&sky_color(&config, height).to_srgba().to_u8_array());
+            }
+        }
+    }
+
+    let mut image = Image::new(
+        Extent3d {
+            width: FACE_SIZE,
+            height: FACE_SIZE,
+            depth_or_array_layers: 6,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_view_descriptor = Some(TextureViewDescriptor {
+        dimension: Some(TextureViewDimension::Cube),
+        ..default()
+    });
+
+    commands.insert_resource(EnvAssets {
+        sky: images.add(image),
+    });
+}
+
+/// Colour of the sky at `height`, the vertical part of a unit direction.
+fn sky_color(config: &SkyConfig, height: f32) -> Color {
+    if height >= 0.0 {
+        // Eased, so the pale band hugs the horizon.
+        config.horizon.mix(&config.zenith, height.sqrt())
+    } else {
+        config.horizon.mix(&config.ground, (-height * 4.0).min(1.0))
+    }
+}
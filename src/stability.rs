@@ -0,0 +1,75 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use crate::settings::Settings;
+use crate::{GameState, Player};
+
+/// Optional guard against the ball tunnelling through atoms and thin geometry
+/// at high speed, toggled by `Settings::high_speed_stability`.
+///
+/// Extra substeps make every fixed tick's solver run that many more times for
+/// every body, so the cost grows with the whole scene. Swept CCD only costs a
+/// shape cast per tick for the player, and only while it's moving fast.
+pub struct StabilityPlugin;
+
+impl Plugin for StabilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StabilityConfig>().add_systems(
+            Update,
+            (
+                apply_substeps.run_if(resource_changed::<Settings>),
+                toggle_player_ccd.run_if(in_state(GameState::Game)),
+            ),
+        );
+    }
+}
+
+#[derive(Resource)]
+pub struct StabilityConfig {
+    /// Solver substeps per fixed tick while enabled. Avian's default is 6.
+    pub substeps: u32,
+    /// Player speed above which swept CCD is turned on.
+    pub ccd_speed: f32,
+}
+
+impl Default for StabilityConfig {
+    fn default() -> Self {
+        Self {
+            substeps: 12,
+            ccd_speed: 30.0,
+        }
+    }
+}
+
+fn apply_substeps(
+    settings: Res<Settings>,
+    config: Res<StabilityConfig>,
+    mut substeps: ResMut<SubstepCount>,
+) {
+    let count = if settings.high_speed_stability {
+        config.substeps
+    } else {
+        SubstepCount::default().0
+    };
+    if substeps.0 != count {
+        substeps.0 = count;
+    }
+}
+
+fn toggle_player_ccd(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    config: Res<StabilityConfig>,
+    player: Query<(Entity, &LinearVelocity, Has<SweptCcd>), With<Player>>,
+) {
+    for (entity, velocity, has_ccd) in player.iter() {
+        let wants_ccd =
+            settings.high_speed_stability && velocity.length_squared() > config.ccd_speed.powi(2);
+
+        if wants_ccd && !has_ccd {
+            commands.entity(entity).insert(SweptCcd::default());
+        } else if !wants_ccd && has_ccd {
+            commands.entity(entity).remove::<SweptCcd>();
+        }
+    }
+}
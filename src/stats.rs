@@ -0,0 +1,414 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::render::view::RenderLayers;
+use bevy::time::common_conditions::on_timer;
+use bevy::ui::FocusPolicy;
+use bevy_third_person_camera::ThirdPersonCamera;
+use serde::{Deserialize, Serialize};
+
+use crate::high_score::HighScore;
+use crate::menu_nav::Modal;
+use crate::storage;
+use crate::{FontAssets, GameState, Menu, NORMAL_BUTTON};
+
+const STATS_PATH: &str = "stats.ron";
+
+/// Bumped whenever the meaning of a saved field changes. New fields don't
+/// need it, since missing ones fall back to their defaults.
+const STATS_VERSION: u32 = 1;
+
+/// Collected atoms and play time are counted straight away but only written
+/// out this often, and whenever the game state changes.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Lifetime atom totals that get a toast when reached.
+const MILESTONES: [u64; 6] = [1_000, 5_000, 10_000, 25_000, 50_000, 100_000];
+
+const TOAST_SECS: f32 = 3.0;
+
+/// Persistent player records, kept separate from settings so resetting one
+/// never touches the other. The statistics screen off the main menu shows
+/// the lifetime totals and can reset them.
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_stats())
+            .init_resource::<UnsavedAtoms>()
+            .init_resource::<UnsavedPlayTime>()
+            .add_systems(
+                Update,
+                (
+                    milestone_toast.run_if(in_state(GameState::Game)),
+                    expire_toasts,
+                    lifetime_atoms_text.run_if(in_state(GameState::Menu)),
+                    setup_stats_screen.run_if(in_state(GameState::Menu).and(run_once)),
+                    stats_button_system
+                        .run_if(in_state(GameState::Menu).or(in_state(GameState::Statistics))),
+                    stats_text.run_if(
+                        in_state(GameState::Statistics)
+                            .and(resource_changed::<Stats>.or(resource_changed::<HighScore>)),
+                    ),
+                ),
+            )
+            .add_systems(OnEnter(GameState::Statistics), stats_text)
+            .add_systems(OnExit(GameState::Statistics), close_reset_confirm)
+            .add_systems(
+                Last,
+                (
+                    flush_unsaved.run_if(
+                        on_timer(FLUSH_INTERVAL)
+                            .or(state_changed::<GameState>)
+                            .or(on_event::<AppExit>),
+                    ),
+                    save_stats.run_if(resource_changed::<Stats>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Resource, Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stats {
+    /// Save format version. Files from before it was added read as 0.
+    pub version: u32,
+    /// Best route efficiency percentage, by level name.
+    pub best_efficiency: HashMap<String, f32>,
+    /// Set once the intro has been seen, so it only plays on the first run.
+    pub has_played: bool,
+    /// Set once the controls tutorial has been dismissed.
+    pub seen_tutorial: bool,
+    /// Every atom collected, across all runs.
+    pub atoms_collected: u64,
+    /// Name of the level played most recently, selected again on startup.
+    pub last_level: Option<String>,
+    /// Names of the levels won at least once. Each one unlocks the next.
+    pub completed_levels: HashSet<String>,
+    /// Runs that ended in a win or a loss.
+    pub runs: u64,
+    /// Time spent in runs, from "Go" to the end, across all runs.
+    pub play_time: Duration,
+}
+
+/// Atoms collected since `Stats` was last written. Kept apart so a pickup
+/// doesn't mean a save.
+#[derive(Resource, Default)]
+pub struct UnsavedAtoms(pub u64);
+
+/// Run time since `Stats` was last written, added to by the run timer.
+#[derive(Resource, Default)]
+pub struct UnsavedPlayTime(pub Duration);
+
+#[derive(Component)]
+pub struct LifetimeAtomsText;
+
+#[derive(Component)]
+struct MilestoneToast {
+    timer: Timer,
+}
+
+fn load_stats() -> Stats {
+    let mut stats = storage::load::<Stats>(STATS_PATH);
+    if stats.version > STATS_VERSION {
+        warn!(
+            "{STATS_PATH} is from a newer version ({}), fields may be lost",
+            stats.version
+        );
+    }
+    // Nothing to migrate yet; version 1 only added fields.
+    stats.version = STATS_VERSION;
+    stats
+}
+
+fn flush_unsaved(
+    mut stats: ResMut<Stats>,
+    mut unsaved_atoms: ResMut<UnsavedAtoms>,
+    mut unsaved_time: ResMut<UnsavedPlayTime>,
+) {
+    if unsaved_atoms.0 == 0 && unsaved_time.0.is_zero() {
+        return;
+    }
+    stats.atoms_collected += std::mem::take(&mut unsaved_atoms.0);
+    stats.play_time += std::mem::take(&mut unsaved_time.0);
+}
+
+fn save_stats(stats: Res<Stats>) {
+    storage::save(STATS_PATH, &*stats);
+}
+
+fn milestone_toast(
+    mut commands: Commands,
+    mut last_total: Local<Option<u64>>,
+    stats: Res<Stats>,
+    unsaved: Res<UnsavedAtoms>,
+    font_assets: Res<FontAssets>,
+    camera: Single<Entity, With<ThirdPersonCamera>>,
+) {
+    let total = stats.atoms_collected + unsaved.0;
+    let last = last_total.replace(total).unwrap_or(total);
+    let Some(milestone) = MILESTONES
+        .iter()
+        .rev()
+        .find(|&&milestone| last < milestone && milestone <= total)
+    else {
+        return;
+    };
+
+    commands.spawn((
+        MilestoneToast {
+            timer: Timer::from_seconds(TOAST_SECS, TimerMode::Once),
+        },
+        UiTargetCamera(*camera),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(80.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        children![(
+            Text::new(format!("{milestone} atoms collected!")),
+            TextFont {
+                font: font_assets.u_atom.clone(),
+                font_size: 42.0,
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.85, 0.3)),
+            TextShadow::default(),
+        )],
+    ));
+}
+
+fn expire_toasts(
+    mut commands: Commands,
+    mut toasts: Query<(Entity, &mut MilestoneToast)>,
+    time: Res<Time>,
+) {
+    for (entity, mut toast) in toasts.iter_mut() {
+        if toast.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn lifetime_atoms_text(
+    stats: Res<Stats>,
+    unsaved: Res<UnsavedAtoms>,
+    mut text_query: Query<&mut Text, With<LifetimeAtomsText>>,
+) {
+    if !stats.is_changed() && !unsaved.is_changed() {
+        return;
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.0 = format!("Atoms collected: {}", stats.atoms_collected + unsaved.0);
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+pub enum StatsMenuAction {
+    Open,
+    Reset,
+    ConfirmReset,
+    CancelReset,
+    Back,
+}
+
+#[derive(Component)]
+struct StatsText;
+
+#[derive(Component)]
+struct ResetConfirmPanel;
+
+fn setup_stats_screen(mut commands: Commands, font_assets: Res<FontAssets>) {
+    commands.spawn((
+        Menu {
+            show_state: GameState::Statistics,
+        },
+        RenderLayers::layer(1),
+        Visibility::Hidden,
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(10.0),
+            ..default()
+        },
+        children![
+            (
+                Text::new("Statistics"),
+                TextFont {
+                    font: font_assets.u_atom.clone(),
+                    font_size: 100.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextShadow::default(),
+            ),
+            (
+                StatsText,
+                Text::default(),
+                TextFont {
+                    font: font_assets.u_atom.clone(),
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextLayout::new_with_justify(JustifyText::Center),
+                TextShadow::default(),
+            ),
+            stats_button("Reset", StatsMenuAction::Reset, 300.0, &font_assets),
+            stats_button("Back", StatsMenuAction::Back, 300.0, &font_assets),
+        ],
+    ));
+
+    commands.spawn((
+        ResetConfirmPanel,
+        Modal,
+        RenderLayers::layer(1),
+        Visibility::Hidden,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(20.0),
+            ..default()
+        },
+        // Keeps clicks off the screen underneath.
+        FocusPolicy::Block,
+        GlobalZIndex(10),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        children![
+            (
+                Text::new("Reset all statistics?"),
+                TextFont {
+                    font: font_assets.u_atom.clone(),
+                    font_size: 60.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextShadow::default(),
+            ),
+            (
+                Node {
+                    column_gap: Val::Px(20.0),
+                    ..default()
+                },
+                children![
+                    stats_button("Yes", StatsMenuAction::ConfirmReset, 160.0, &font_assets),
+                    stats_button("No", StatsMenuAction::CancelReset, 160.0, &font_assets),
+                ],
+            ),
+        ],
+    ));
+}
+
+fn stats_button(
+    label: &str,
+    action: StatsMenuAction,
+    width: f32,
+    assets: &FontAssets,
+) -> impl Bundle + use<> {
+    (
+        Button,
+        action,
+        Node {
+            width: Val::Px(width),
+            height: Val::Px(80.0),
+            border: UiRect::all(Val::Px(5.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BorderColor(Color::BLACK),
+        BorderRadius::MAX,
+        BackgroundColor(NORMAL_BUTTON),
+        children![(
+            Text::new(label),
+            TextFont {
+                font: assets.u_atom.clone(),
+                font_size: 38.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+            TextShadow::default(),
+        )],
+    )
+}
+
+/// Resetting clears the lifetime totals and best times, but keeps level
+/// unlocks and the one-time intro and tutorial.
+fn stats_button_system(
+    interaction_query: Query<
+        (&Interaction, &StatsMenuAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut stats: ResMut<Stats>,
+    mut unsaved_atoms: ResMut<UnsavedAtoms>,
+    mut unsaved_time: ResMut<UnsavedPlayTime>,
+    mut high_score: ResMut<HighScore>,
+    mut confirm: Single<&mut Visibility, With<ResetConfirmPanel>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, action) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match *action {
+            StatsMenuAction::Open => next_state.set(GameState::Statistics),
+            StatsMenuAction::Reset => **confirm = Visibility::Inherited,
+            StatsMenuAction::ConfirmReset => {
+                stats.atoms_collected = 0;
+                stats.runs = 0;
+                stats.play_time = Duration::ZERO;
+                stats.best_efficiency.clear();
+                unsaved_atoms.0 = 0;
+                unsaved_time.0 = Duration::ZERO;
+                high_score.best_times.clear();
+                **confirm = Visibility::Hidden;
+            }
+            StatsMenuAction::CancelReset => **confirm = Visibility::Hidden,
+            StatsMenuAction::Back => next_state.set(GameState::Menu),
+        }
+    }
+}
+
+fn close_reset_confirm(mut confirm: Single<&mut Visibility, With<ResetConfirmPanel>>) {
+    **confirm = Visibility::Hidden;
+}
+
+fn stats_text(
+    stats: Res<Stats>,
+    unsaved_atoms: Res<UnsavedAtoms>,
+    unsaved_time: Res<UnsavedPlayTime>,
+    high_score: Res<HighScore>,
+    mut text_query: Query<&mut Text, With<StatsText>>,
+) {
+    let play_secs = (stats.play_time + unsaved_time.0).as_secs();
+    let best = high_score
+        .best_times
+        .iter()
+        .min_by(|a, b| a.1.total_cmp(b.1))
+        .map_or("-".to_string(), |(level, time)| {
+            format!("{time:.2}s on {level}")
+        });
+    let label = format!(
+        "Atoms detonated: {}\nRuns: {}\nTime played: {}h {:02}m {:02}s\nBest time: {best}",
+        stats.atoms_collected + unsaved_atoms.0,
+        stats.runs,
+        play_secs / 3600,
+        play_secs / 60 % 60,
+        play_secs % 60,
+    );
+    for mut text in text_query.iter_mut() {
+        text.0.clone_from(&label);
+    }
+}
@@ -0,0 +1,73 @@
+//! Small RON files for settings and stats, at paths relative to the working
+//! directory. WASM builds have no filesystem, so there each file is kept in
+//! the browser's local storage under its path instead.
+
+use bevy::prelude::*;
+use serde::{Serialize, de::DeserializeOwned};
+
+pub fn load<T: DeserializeOwned + Default>(path: &str) -> T {
+    let Ok(contents) = read_string(path) else {
+        return T::default();
+    };
+
+    ron::from_str(&contents).unwrap_or_else(|err| {
+        // The defaults get saved over it, so keep a copy to recover from.
+        let backup = format!("{path}.bak");
+        match write_string(&backup, &contents) {
+            Ok(()) => warn!("Ignoring malformed {path}, kept as {backup}: {err}"),
+            Err(backup_err) => {
+                warn!("Ignoring malformed {path}: {err}, and could not keep a copy: {backup_err}")
+            }
+        }
+        T::default()
+    })
+}
+
+/// Like `load`, but reports a missing or malformed file instead of falling
+/// back to a default.
+pub fn read<T: DeserializeOwned>(path: &str) -> Result<T, String> {
+    let contents = read_string(path)?;
+    ron::from_str(&contents).map_err(|err| err.to_string())
+}
+
+pub fn save<T: Serialize>(path: &str, value: &T) {
+    let result = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(|err| err.to_string())
+        .and_then(|contents| write_string(path, &contents));
+
+    if let Err(err) = result {
+        warn!("Failed to write {path}: {err}");
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_string(path: &str) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|err| err.to_string())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_string(path: &str, contents: &str) -> Result<(), String> {
+    std::fs::write(path, contents).map_err(|err| err.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Result<web_sys::Storage, String> {
+    web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .ok_or_else(|| "local storage unavailable".to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_string(path: &str) -> Result<String, String> {
+    local_storage()?
+        .get_item(path)
+        .map_err(|err| format!("{err:?}"))?
+        .ok_or_else(|| "not found".to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write_string(path: &str, contents: &str) -> Result<(), String> {
+    local_storage()?
+        .set_item(path, contents)
+        .map_err(|err| format!("{err:?}"))
+}
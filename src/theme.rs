@@ -0,0 +1,127 @@
+use bevy::{color::palettes::css, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::settings::Settings;
+
+/// Colours that carry meaning, swapped out as a set for players who can't
+/// tell the default red and green apart. A new theme repaints the menus
+/// straight away; the player and capture bars pick it up when next spawned.
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreStartup, load_color_theme).add_systems(
+            Update,
+            (
+                apply_color_preset.run_if(resource_changed::<Settings>),
+                paint_buttons,
+            )
+                .chain(),
+        );
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum ColorPreset {
+    Default,
+    Deuteranopia,
+    Protanopia,
+}
+
+impl ColorPreset {
+    const ALL: [ColorPreset; 3] = [
+        ColorPreset::Default,
+        ColorPreset::Deuteranopia,
+        ColorPreset::Protanopia,
+    ];
+
+    /// The next (positive `step`) or previous preset, wrapping round.
+    pub fn step(self, step: i32) -> Self {
+        let index = Self::ALL.iter().position(|p| *p == self).unwrap_or(0) as i32;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as i32) as usize]
+    }
+}
+
+#[derive(Resource, Clone, PartialEq, Debug)]
+pub struct ColorTheme {
+    pub player: Color,
+    /// Progress towards collecting an atom.
+    pub highlight: Color,
+    pub button_normal: Color,
+    pub button_hovered: Color,
+    pub button_pressed: Color,
+    pub button_pressed_border: Color,
+}
+
+impl ColorTheme {
+    pub fn new(preset: ColorPreset) -> Self {
+        let default = Self {
+            player: css::DARK_CYAN.into(),
+            highlight: Color::srgb(0.35, 0.75, 0.35),
+            button_normal: Color::srgb(0.15, 0.15, 0.15),
+            button_hovered: Color::srgb(0.25, 0.25, 0.25),
+            button_pressed: Color::srgb(0.35, 0.75, 0.35),
+            button_pressed_border: css::RED.into(),
+        };
+
+        // Blues against oranges and yellows, from the Okabe-Ito palette.
+        match preset {
+            ColorPreset::Default => default,
+            ColorPreset::Deuteranopia => Self {
+                player: Color::srgb(0.0, 0.45, 0.7),
+                highlight: Color::srgb(0.9, 0.6, 0.0),
+                button_pressed: Color::srgb(0.0, 0.45, 0.7),
+                button_pressed_border: Color::srgb(0.9, 0.6, 0.0),
+                ..default
+            },
+            ColorPreset::Protanopia => Self {
+                player: Color::srgb(0.35, 0.7, 0.9),
+                highlight: Color::srgb(0.95, 0.9, 0.25),
+                button_pressed: Color::srgb(0.0, 0.45, 0.7),
+                button_pressed_border: Color::srgb(0.95, 0.9, 0.25),
+                ..default
+            },
+        }
+    }
+
+    /// Background and border for a button in the given state.
+    pub fn button(&self, interaction: Interaction) -> (Color, Color) {
+        match interaction {
+            Interaction::Pressed => (self.button_pressed, self.button_pressed_border),
+            Interaction::Hovered => (self.button_hovered, Color::WHITE),
+            Interaction::None => (self.button_normal, Color::BLACK),
+        }
+    }
+}
+
+fn load_color_theme(mut commands: Commands, settings: Res<Settings>) {
+    commands.insert_resource(ColorTheme::new(settings.color_theme));
+}
+
+fn apply_color_preset(settings: Res<Settings>, mut theme: ResMut<ColorTheme>) {
+    let new = ColorTheme::new(settings.color_theme);
+    if *theme != new {
+        *theme = new;
+    }
+}
+
+/// Buttons are spawned in the default colours, so new ones are repainted
+/// here, and every one of them when the theme changes.
+fn paint_buttons(
+    theme: Res<ColorTheme>,
+    mut buttons: Query<(
+        Ref<Button>,
+        &Interaction,
+        &mut BackgroundColor,
+        &mut BorderColor,
+    )>,
+) {
+    for (button, interaction, mut color, mut border_color) in buttons.iter_mut() {
+        if !theme.is_changed() && !button.is_added() {
+            continue;
+        }
+        let (background, border) = theme.button(*interaction);
+        color.set_if_neq(BackgroundColor(background));
+        border_color.set_if_neq(BorderColor(border));
+    }
+}
@@ -0,0 +1,141 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_third_person_camera::ThirdPersonCamera;
+
+use crate::controls::{KeyBindings, key_name};
+use crate::stats::Stats;
+use crate::{FontAssets, GameState, Menu};
+
+/// Shows the basic controls over the first run, until any key is pressed or
+/// it times out. `Stats::seen_tutorial` keeps it from coming back, and the
+/// settings menu can clear that to show it again.
+pub struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TutorialConfig>().add_systems(
+            Update,
+            (
+                // The other way round, the key press that started the run could
+                // dismiss it straight away.
+                dismiss_tutorial,
+                show_tutorial.run_if(not(any_with_component::<Tutorial>).and(tutorial_unseen)),
+            )
+                .chain()
+                .run_if(in_state(GameState::Game)),
+        );
+    }
+}
+
+#[derive(Resource)]
+pub struct TutorialConfig {
+    /// How long the overlay stays up without a key press. Only counts while
+    /// playing, so pausing doesn't use it up.
+    pub timeout: Duration,
+}
+
+impl Default for TutorialConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(12),
+        }
+    }
+}
+
+#[derive(Component)]
+struct Tutorial(Timer);
+
+fn tutorial_unseen(stats: Res<Stats>) -> bool {
+    !stats.seen_tutorial
+}
+
+fn show_tutorial(
+    mut commands: Commands,
+    config: Res<TutorialConfig>,
+    bindings: Res<KeyBindings>,
+    font_assets: Res<FontAssets>,
+    camera: Single<(Entity, &ThirdPersonCamera)>,
+) {
+    let (camera, third_person) = *camera;
+    let controls = format!(
+        "{}{}{}{} to move\n{} to jump\n{} to lock the cursor\n{} to pause",
+        key_name(bindings.forward),
+        key_name(bindings.left),
+        key_name(bindings.back),
+        key_name(bindings.right),
+        key_name(bindings.jump),
+        key_name(third_person.cursor_lock_key),
+        key_name(bindings.pause),
+    );
+
+    commands.spawn((
+        Tutorial(Timer::new(config.timeout, TimerMode::Once)),
+        Menu {
+            show_state: GameState::Game,
+        },
+        UiTargetCamera(camera),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        // Above the HUD.
+        ZIndex(1),
+        children![(
+            Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                padding: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            BorderRadius::all(Val::Px(20.0)),
+            children![
+                (
+                    Text::new(controls),
+                    TextFont {
+                        font: font_assets.u_atom.clone(),
+                        font_size: 38.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    TextLayout::new_with_justify(JustifyText::Center),
+                    TextShadow::default(),
+                ),
+                (
+                    Text::new("Press any key to continue"),
+                    TextFont {
+                        font: font_assets.u_atom.clone(),
+                        font_size: 24.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                    TextShadow::default(),
+                ),
+            ],
+        )],
+    ));
+}
+
+/// The key that dismisses it still does its usual job, so pressing the pause
+/// key both dismisses and pauses.
+fn dismiss_tutorial(
+    mut commands: Commands,
+    time: Res<Time>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut stats: ResMut<Stats>,
+    mut tutorial: Query<(Entity, &mut Tutorial)>,
+) {
+    let pressed = keyboard.get_just_pressed().next().is_some();
+    for (entity, mut tutorial) in tutorial.iter_mut() {
+        if pressed || tutorial.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            stats.seen_tutorial = true;
+        }
+    }
+}
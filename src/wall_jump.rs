@@ -0,0 +1,110 @@
+use std::f32::consts::TAU;
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_tnua::prelude::*;
+
+use crate::GameState;
+
+/// Lets an airborne player jump off a wall beside them. The jump itself is
+/// the usual one from `apply_controls`, allowed in the air while a wall is
+/// in reach; the push away from the wall is added here as it starts.
+pub struct WallJumpPlugin;
+
+impl Plugin for WallJumpPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WallJumpConfig>().add_systems(
+            FixedUpdate,
+            // The push reads the wall found before the jump was fed, so it
+            // runs before that is replaced.
+            (push_off_wall, find_wall)
+                .chain()
+                .before(TnuaUserControlsSystemSet)
+                .run_if(in_state(GameState::Game)),
+        );
+    }
+}
+
+#[derive(Resource)]
+pub struct WallJumpConfig {
+    pub enabled: bool,
+    /// How far from the ball's centre a wall can be jumped off.
+    pub reach: f32,
+    /// Steepest a surface's normal can point up and still count as a wall,
+    /// so slopes and ledges don't.
+    pub max_normal_y: f32,
+    /// Speed the jump carries the player away from the wall.
+    pub push_speed: f32,
+}
+
+impl Default for WallJumpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            reach: 0.8,
+            max_normal_y: 0.3,
+            push_speed: 12.0,
+        }
+    }
+}
+
+/// The level direction away from a wall in reach, while the player is in
+/// the air. Always `None` on the ground.
+#[derive(Component, Default, PartialEq)]
+pub struct WallContact(pub Option<Vec3>);
+
+/// Directions checked for a wall around the ball.
+const WALL_PROBES: usize = 8;
+
+fn find_wall(
+    config: Res<WallJumpConfig>,
+    spatial_query: SpatialQuery,
+    sensors: Query<(), With<Sensor>>,
+    mut players: Query<(Entity, &TnuaController, &Position, &mut WallContact)>,
+) {
+    for (entity, controller, position, mut contact) in players.iter_mut() {
+        let airborne = controller.is_airborne().unwrap_or(false);
+        if !config.enabled || !airborne {
+            contact.set_if_neq(WallContact(None));
+            continue;
+        }
+
+        let filter = SpatialQueryFilter::from_excluded_entities([entity]);
+        let wall = (0..WALL_PROBES)
+            .filter_map(|i| {
+                let angle = i as f32 / WALL_PROBES as f32 * TAU;
+                let direction = Dir3::new(Vec3::new(angle.cos(), 0.0, angle.sin())).ok()?;
+                spatial_query.cast_ray_predicate(
+                    position.0,
+                    direction,
+                    config.reach,
+                    true,
+                    &filter,
+                    // Atoms and hazards the ball passes through aren't walls.
+                    &|hit| !sensors.contains(hit),
+                )
+            })
+            .filter(|hit| hit.normal.y.abs() <= config.max_normal_y)
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+            .and_then(|hit| Vec3::new(hit.normal.x, 0.0, hit.normal.z).try_normalize());
+        contact.set_if_neq(WallContact(wall));
+    }
+}
+
+fn push_off_wall(
+    config: Res<WallJumpConfig>,
+    mut players: Query<(&TnuaController, &WallContact, &mut LinearVelocity)>,
+) {
+    for (controller, contact, mut velocity) in players.iter_mut() {
+        let Some(away) = contact.0 else {
+            continue;
+        };
+        if controller.action_flow_status().just_starting() != Some(TnuaBuiltinJump::NAME) {
+            continue;
+        }
+
+        // Whatever speed was carrying the ball into the wall is dropped.
+        let into_wall = velocity.0.dot(away).min(0.0);
+        velocity.0 += away * (config.push_speed - into_wall);
+    }
+}